@@ -0,0 +1,7 @@
+//! Debugging subsystems
+//!
+//! Everything here is optional tooling for ROM-hackers and homebrew
+//! developers; none of it is required by the core emulation loop.
+
+#[cfg(feature = "gdb")]
+pub mod gdb;
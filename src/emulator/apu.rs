@@ -2,12 +2,16 @@
 //!
 //! Handles the Game Boy's 4-channel audio synthesis.
 
+use crate::emulator::audio::{AudioInterface, NullAudio, StereoFrame};
+
+/// Frame sequencer period in cycles (512 Hz at the 4.194304 MHz clock).
+/// `pub(crate)` so `GameBoy::step` can re-arm the scheduler's
+/// `ApuFrameSequencer` event after dispatching it.
+pub(crate) const FRAME_SEQUENCER_PERIOD: u64 = 8192;
+
 /// Audio sample rate
 pub const SAMPLE_RATE: u32 = 44100;
 
-/// Audio buffer size
-pub const BUFFER_SIZE: usize = 1024;
-
 /// Audio channel types
 #[derive(Debug, Clone, Copy)]
 pub enum ChannelType {
@@ -17,31 +21,113 @@ pub enum ChannelType {
     Noise,
 }
 
+/// Width (in output samples) of the band-limited step kernel.
+const BLIP_WIDTH: usize = 8;
+
+/// Precomputed band-limited step response: a smoothed transition from 0 to
+/// 1 spread across `BLIP_WIDTH` samples, so a hard amplitude edge in a
+/// channel's waveform doesn't alias back into the audible band the way an
+/// instantaneous jump would when downsampled to the output rate.
+const BLIP_STEP: [f32; BLIP_WIDTH] = [0.07, 0.24, 0.45, 0.66, 0.83, 0.93, 0.98, 1.0];
+
+/// How the ~4.19MHz native APU clock is downsampled to `SAMPLE_RATE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownsampleType {
+    /// Emit the most recent raw sample as-is (the original behavior).
+    NearestNeighbor,
+    /// Average the current and previous raw sample (cheap low-pass).
+    Linear,
+    /// Spread each amplitude step across several output samples with a
+    /// band-limited kernel instead of emitting it as a hard edge.
+    BandLimited,
+}
+
+/// Spreads hard amplitude steps across several output samples via a small
+/// FIR delta buffer instead of emitting them as an instantaneous jump, the
+/// "blip" technique used by band-limited synthesis libraries.
+struct BlipSynth {
+    delta: [f32; BLIP_WIDTH],
+    position: usize,
+    last_amplitude: f32,
+    integral: f32,
+}
+
+impl BlipSynth {
+    fn new() -> Self {
+        Self {
+            delta: [0.0; BLIP_WIDTH],
+            position: 0,
+            last_amplitude: 0.0,
+            integral: 0.0,
+        }
+    }
+
+    /// Feed one raw (unfiltered) amplitude and return the band-limited
+    /// output sample for this tick.
+    fn step(&mut self, amplitude: f32) -> f32 {
+        let edge = amplitude - self.last_amplitude;
+        if edge != 0.0 {
+            let mut prev_weight = 0.0;
+            for i in 0..BLIP_WIDTH {
+                let weight = BLIP_STEP[i];
+                let idx = (self.position + i) % BLIP_WIDTH;
+                self.delta[idx] += edge * (weight - prev_weight);
+                prev_weight = weight;
+            }
+            self.last_amplitude = amplitude;
+        }
+
+        self.integral += self.delta[self.position];
+        self.delta[self.position] = 0.0;
+        self.position = (self.position + 1) % BLIP_WIDTH;
+        self.integral
+    }
+}
+
 /// Audio Processing Unit
 pub struct Apu {
-    /// Master volume and enable
+    /// NR50: bits 4-6 are the left (SO2) volume, bits 0-2 the right (SO1)
+    /// volume, both 0-7.
     pub master_volume: u8,
     pub sound_enabled: bool,
-    
-    /// Audio output buffer
-    pub audio_buffer: [i16; BUFFER_SIZE],
-    pub buffer_pos: usize,
-    
+
+    /// NR51: per-channel left/right panning. Bit `n` routes channel
+    /// `n % 4 + 1` to the right (SO1) output, bit `n + 4` to the left (SO2).
+    pub panning: u8,
+
+    /// Selects how the native clock is downsampled to `SAMPLE_RATE`.
+    pub downsample_type: DownsampleType,
+
+    /// Last raw (pre-downsample) stereo sample, used by `Linear` mode.
+    prev_raw: (i16, i16),
+
+    /// Band-limited synthesis state, used by `BandLimited` mode.
+    blip_left: BlipSynth,
+    blip_right: BlipSynth,
+
+    /// Producer half of the audio output interface; rendered frames are
+    /// pushed here instead of into a fixed-size buffer. Defaults to
+    /// `NullAudio` until a real sink is attached with `set_audio_interface`.
+    pub audio: Box<dyn AudioInterface>,
+
     /// Cycle counter for timing
     pub cycles: u32,
-    
+
     /// Sample counter
     pub sample_counter: u32,
-    
+
+    /// Frame sequencer step (0-7), advances at 512 Hz
+    pub frame_sequencer_step: u8,
+
     /// Channel 1 (Square wave with sweep)
     pub channel1: SquareChannel,
-    
+
     /// Channel 2 (Square wave)
     pub channel2: SquareChannel,
-    
+
     /// Channel 3 (Wave)
     pub channel3: WaveChannel,
-    
+
     /// Channel 4 (Noise)
     pub channel4: NoiseChannel,
 }
@@ -56,11 +142,21 @@ pub struct SquareChannel {
     pub envelope_direction: bool,
     pub length: u8,
     pub length_enabled: bool,
-    
+
+    /// Sweep period, direction (`true` = increase), and shift. Only
+    /// channel 1 has a sweep unit on real hardware; channel 2 ignores it.
+    pub sweep_period: u8,
+    pub sweep_direction: bool,
+    pub sweep_shift: u8,
+
+    /// MIDI note currently sounding via `Apu::note_on`, if any.
+    pub active_note: Option<u8>,
+
     // Internal state
     pub phase: u32,
     pub envelope_counter: u8,
     pub current_volume: u8,
+    pub sweep_counter: u8,
 }
 
 /// Wave channel
@@ -71,7 +167,10 @@ pub struct WaveChannel {
     pub length: u8,
     pub length_enabled: bool,
     pub wave_pattern: [u8; 32],
-    
+
+    /// MIDI note currently sounding via `Apu::note_on`, if any.
+    pub active_note: Option<u8>,
+
     // Internal state
     pub phase: u32,
     pub sample_index: usize,
@@ -87,7 +186,10 @@ pub struct NoiseChannel {
     pub length: u8,
     pub length_enabled: bool,
     pub width_mode: bool,
-    
+
+    /// MIDI note currently sounding via `Apu::note_on`, if any.
+    pub active_note: Option<u8>,
+
     // Internal state
     pub lfsr: u16,
     pub envelope_counter: u8,
@@ -100,10 +202,15 @@ impl Apu {
         Self {
             master_volume: 0x77,
             sound_enabled: true,
-            audio_buffer: [0; BUFFER_SIZE],
-            buffer_pos: 0,
+            panning: 0xFF,
+            downsample_type: DownsampleType::NearestNeighbor,
+            prev_raw: (0, 0),
+            blip_left: BlipSynth::new(),
+            blip_right: BlipSynth::new(),
+            audio: Box::new(NullAudio),
             cycles: 0,
             sample_counter: 0,
+            frame_sequencer_step: 0,
             channel1: SquareChannel::new(),
             channel2: SquareChannel::new(),
             channel3: WaveChannel::new(),
@@ -111,63 +218,154 @@ impl Apu {
         }
     }
 
-    /// Step the APU by one cycle
+    /// Step the APU by one cycle. The frame sequencer is driven separately
+    /// by `GameBoy::step` via the scheduler, since it no longer fires once
+    /// per cycle.
     pub fn step(&mut self) {
         self.cycles += 1;
-        
+
         // Generate audio samples at 44.1kHz
         // Game Boy runs at ~4.194MHz, so we need to downsample
         self.sample_counter += SAMPLE_RATE;
-        
+
         if self.sample_counter >= 4194304 {
             self.sample_counter -= 4194304;
             self.generate_sample();
         }
     }
 
-    /// Generate one audio sample
+    /// Advance the 512 Hz frame sequencer by one step.
+    ///
+    /// The sequencer cycles through 8 steps (0-7): length counters clock at
+    /// 256 Hz on even steps, the sweep unit at 128 Hz on steps 2 and 6, and
+    /// the volume envelope at 64 Hz on step 7. Called by `GameBoy::step`
+    /// when the scheduler's `ApuFrameSequencer` event comes due; re-arming
+    /// that event is the caller's responsibility.
+    pub(crate) fn clock_frame_sequencer(&mut self) {
+        if self.frame_sequencer_step % 2 == 0 {
+            self.clock_length_counters();
+        }
+        if self.frame_sequencer_step == 2 || self.frame_sequencer_step == 6 {
+            self.channel1.clock_sweep();
+        }
+        if self.frame_sequencer_step == 7 {
+            self.clock_envelopes();
+        }
+
+        self.frame_sequencer_step = (self.frame_sequencer_step + 1) % 8;
+    }
+
+    /// Clock the 256 Hz length counters, disabling channels that expire.
+    fn clock_length_counters(&mut self) {
+        for channel in [&mut self.channel1, &mut self.channel2] {
+            if channel.length_enabled && channel.length > 0 {
+                channel.length -= 1;
+                if channel.length == 0 {
+                    channel.enabled = false;
+                }
+            }
+        }
+        if self.channel3.length_enabled && self.channel3.length > 0 {
+            self.channel3.length -= 1;
+            if self.channel3.length == 0 {
+                self.channel3.enabled = false;
+            }
+        }
+        if self.channel4.length_enabled && self.channel4.length > 0 {
+            self.channel4.length -= 1;
+            if self.channel4.length == 0 {
+                self.channel4.enabled = false;
+            }
+        }
+    }
+
+    /// Clock the 64 Hz volume envelopes for the two square channels and noise.
+    fn clock_envelopes(&mut self) {
+        for channel in [&mut self.channel1, &mut self.channel2] {
+            channel.clock_envelope();
+        }
+        self.channel4.clock_envelope();
+    }
+
+    /// Attach the sink that rendered frames are pushed into, replacing
+    /// whatever was previously set (`NullAudio` by default).
+    pub fn set_audio_interface(&mut self, audio: Box<dyn AudioInterface>) {
+        self.audio = audio;
+    }
+
+    /// Select how the native clock is downsampled to `SAMPLE_RATE`.
+    pub fn set_downsample_type(&mut self, downsample_type: DownsampleType) {
+        self.downsample_type = downsample_type;
+    }
+
+    /// Route one channel's sample into the left/right accumulators
+    /// according to the NR51 panning bits. `channel_index` is 0-3 for
+    /// channels 1-4.
+    fn mix_channel(&self, channel_index: u8, sample: i32, left: &mut i32, right: &mut i32) {
+        if self.panning & (1 << channel_index) != 0 {
+            *right += sample;
+        }
+        if self.panning & (1 << (channel_index + 4)) != 0 {
+            *left += sample;
+        }
+    }
+
+    /// Generate one audio sample and push it to the attached audio sink
     fn generate_sample(&mut self) {
         if !self.sound_enabled {
-            self.audio_buffer[self.buffer_pos] = 0;
-            self.buffer_pos = (self.buffer_pos + 1) % BUFFER_SIZE;
+            self.push_downsampled(0, 0);
             return;
         }
 
-        // Mix all channels
-        let mut sample = 0i32;
-        
+        let mut left = 0i32;
+        let mut right = 0i32;
+
         if self.channel1.enabled {
-            sample += self.channel1.get_sample() as i32;
+            let sample = self.channel1.get_sample() as i32;
+            self.mix_channel(0, sample, &mut left, &mut right);
         }
-        
+
         if self.channel2.enabled {
-            sample += self.channel2.get_sample() as i32;
+            let sample = self.channel2.get_sample() as i32;
+            self.mix_channel(1, sample, &mut left, &mut right);
         }
-        
+
         if self.channel3.enabled {
-            sample += self.channel3.get_sample() as i32;
+            let sample = self.channel3.get_sample() as i32;
+            self.mix_channel(2, sample, &mut left, &mut right);
         }
-        
+
         if self.channel4.enabled {
-            sample += self.channel4.get_sample() as i32;
+            let sample = self.channel4.get_sample() as i32;
+            self.mix_channel(3, sample, &mut left, &mut right);
         }
 
-        // Apply master volume and convert to 16-bit
-        sample = (sample * (self.master_volume as i32)) / 4;
-        sample = sample.clamp(-32768, 32767);
-        
-        self.audio_buffer[self.buffer_pos] = sample as i16;
-        self.buffer_pos = (self.buffer_pos + 1) % BUFFER_SIZE;
-    }
+        // Apply the NR50 per-side volume and convert to 16-bit.
+        let left_volume = ((self.master_volume >> 4) & 0x07) as i32;
+        let right_volume = (self.master_volume & 0x07) as i32;
+        let left = (left * left_volume / 4).clamp(-32768, 32767) as i16;
+        let right = (right * right_volume / 4).clamp(-32768, 32767) as i16;
 
-    /// Get the current audio samples
-    pub fn get_audio_samples(&self) -> &[i16] {
-        &self.audio_buffer[..self.buffer_pos]
+        self.push_downsampled(left, right);
     }
 
-    /// Clear the audio buffer
-    pub fn clear_buffer(&mut self) {
-        self.buffer_pos = 0;
+    /// Apply the selected `DownsampleType` to one raw stereo sample and
+    /// push the result to the attached audio sink.
+    fn push_downsampled(&mut self, left: i16, right: i16) {
+        let (out_left, out_right) = match self.downsample_type {
+            DownsampleType::NearestNeighbor => (left, right),
+            DownsampleType::Linear => (
+                ((left as i32 + self.prev_raw.0 as i32) / 2) as i16,
+                ((right as i32 + self.prev_raw.1 as i32) / 2) as i16,
+            ),
+            DownsampleType::BandLimited => (
+                self.blip_left.step(left as f32) as i16,
+                self.blip_right.step(right as f32) as i16,
+            ),
+        };
+
+        self.prev_raw = (left, right);
+        self.audio.push_frame(StereoFrame::new(out_left, out_right));
     }
 }
 
@@ -182,9 +380,61 @@ impl SquareChannel {
             envelope_direction: false,
             length: 0,
             length_enabled: false,
+            sweep_period: 0,
+            sweep_direction: false,
+            sweep_shift: 0,
+            active_note: None,
             phase: 0,
             envelope_counter: 0,
             current_volume: 0,
+            sweep_counter: 0,
+        }
+    }
+
+    /// Clock the envelope: every `envelope_period` ticks, step
+    /// `current_volume` up or down by one within 0..=15.
+    fn clock_envelope(&mut self) {
+        if self.envelope_period == 0 {
+            return;
+        }
+
+        self.envelope_counter += 1;
+        if self.envelope_counter >= self.envelope_period {
+            self.envelope_counter = 0;
+            if self.envelope_direction && self.current_volume < 15 {
+                self.current_volume += 1;
+            } else if !self.envelope_direction && self.current_volume > 0 {
+                self.current_volume -= 1;
+            }
+        }
+    }
+
+    /// Clock the frequency sweep unit: every `sweep_period` ticks, shift
+    /// `frequency` by `frequency >> sweep_shift` in the configured direction
+    /// (`true` = increase), disabling the channel if that overflows the
+    /// 11-bit frequency range. A no-op while `sweep_period` is 0.
+    fn clock_sweep(&mut self) {
+        if self.sweep_period == 0 {
+            return;
+        }
+
+        self.sweep_counter += 1;
+        if self.sweep_counter >= self.sweep_period {
+            self.sweep_counter = 0;
+            if self.sweep_shift > 0 {
+                let delta = self.frequency >> self.sweep_shift;
+                let new_frequency = if self.sweep_direction {
+                    self.frequency + delta
+                } else {
+                    self.frequency.saturating_sub(delta)
+                };
+
+                if new_frequency > 2047 {
+                    self.enabled = false;
+                } else {
+                    self.frequency = new_frequency;
+                }
+            }
         }
     }
 
@@ -221,6 +471,7 @@ impl WaveChannel {
             length: 0,
             length_enabled: false,
             wave_pattern: [0; 32],
+            active_note: None,
             phase: 0,
             sample_index: 0,
         }
@@ -250,12 +501,31 @@ impl NoiseChannel {
             length: 0,
             length_enabled: false,
             width_mode: false,
+            active_note: None,
             lfsr: 0x7FFF,
             envelope_counter: 0,
             current_volume: 0,
         }
     }
 
+    /// Clock the envelope: every `envelope_period` ticks, step
+    /// `current_volume` up or down by one within 0..=15.
+    fn clock_envelope(&mut self) {
+        if self.envelope_period == 0 {
+            return;
+        }
+
+        self.envelope_counter += 1;
+        if self.envelope_counter >= self.envelope_period {
+            self.envelope_counter = 0;
+            if self.envelope_direction && self.current_volume < 15 {
+                self.current_volume += 1;
+            } else if !self.envelope_direction && self.current_volume > 0 {
+                self.current_volume -= 1;
+            }
+        }
+    }
+
     fn get_sample(&mut self) -> i16 {
         // Generate noise sample using LFSR
         if self.current_volume == 0 {
@@ -280,6 +550,151 @@ impl NoiseChannel {
     }
 }
 
+/// MIDI-driven synth controller API
+///
+/// Lets the four APU channels be played as a polyphonic synth, driven by
+/// note events instead of (or alongside) a running ROM, so the crate can
+/// serve as the DSP core of a VST/AU plugin or a standalone tracker:
+/// callers push note events each audio block and pull rendered samples
+/// through the ring-buffer audio interface, entirely independent of ROM
+/// execution.
+impl Apu {
+    /// Convert a MIDI note number (plus an optional pitch bend in
+    /// semitones) to the Game Boy's 11-bit frequency value, solving
+    /// `freq = 131072 / (2048 - x)` for `x` given the note's frequency
+    /// in Hz.
+    fn midi_note_to_gb_frequency(midi_note: u8, bend_semitones: f32) -> u16 {
+        let note = midi_note as f32 + bend_semitones;
+        let hz = 440.0 * 2f32.powf((note - 69.0) / 12.0);
+        let x = 2048.0 - 131072.0 / hz;
+        x.round().clamp(0.0, 2047.0) as u16
+    }
+
+    /// Start sounding `midi_note` on `channel` at the given velocity
+    /// (0-127, mapped onto the channel's 4-bit volume).
+    pub fn note_on(&mut self, channel: ChannelType, midi_note: u8, velocity: u8) {
+        let frequency = Self::midi_note_to_gb_frequency(midi_note, 0.0);
+        let volume = ((velocity as u16 * 15) / 127) as u8;
+
+        match channel {
+            ChannelType::Square1 => {
+                self.channel1.active_note = Some(midi_note);
+                self.channel1.frequency = frequency;
+                self.channel1.volume = volume;
+                self.channel1.current_volume = volume;
+                self.channel1.enabled = true;
+            }
+            ChannelType::Square2 => {
+                self.channel2.active_note = Some(midi_note);
+                self.channel2.frequency = frequency;
+                self.channel2.volume = volume;
+                self.channel2.current_volume = volume;
+                self.channel2.enabled = true;
+            }
+            ChannelType::Wave => {
+                self.channel3.active_note = Some(midi_note);
+                self.channel3.frequency = frequency;
+                self.channel3.volume = if velocity == 0 { 0 } else { 1 };
+                self.channel3.enabled = true;
+            }
+            ChannelType::Noise => {
+                self.channel4.active_note = Some(midi_note);
+                self.channel4.frequency = frequency;
+                self.channel4.volume = volume;
+                self.channel4.current_volume = volume;
+                self.channel4.enabled = true;
+            }
+        }
+    }
+
+    /// Stop sounding whatever note is currently playing on `channel`.
+    pub fn note_off(&mut self, channel: ChannelType) {
+        match channel {
+            ChannelType::Square1 => {
+                self.channel1.active_note = None;
+                self.channel1.enabled = false;
+            }
+            ChannelType::Square2 => {
+                self.channel2.active_note = None;
+                self.channel2.enabled = false;
+            }
+            ChannelType::Wave => {
+                self.channel3.active_note = None;
+                self.channel3.enabled = false;
+            }
+            ChannelType::Noise => {
+                self.channel4.active_note = None;
+                self.channel4.enabled = false;
+            }
+        }
+    }
+
+    /// Bend the currently-sounding note on `channel` by `semitones`
+    /// (fractional values are fine). A no-op if the channel isn't active.
+    pub fn pitch_bend(&mut self, channel: ChannelType, semitones: f32) {
+        match channel {
+            ChannelType::Square1 => {
+                if let Some(note) = self.channel1.active_note {
+                    self.channel1.frequency = Self::midi_note_to_gb_frequency(note, semitones);
+                }
+            }
+            ChannelType::Square2 => {
+                if let Some(note) = self.channel2.active_note {
+                    self.channel2.frequency = Self::midi_note_to_gb_frequency(note, semitones);
+                }
+            }
+            ChannelType::Wave => {
+                if let Some(note) = self.channel3.active_note {
+                    self.channel3.frequency = Self::midi_note_to_gb_frequency(note, semitones);
+                }
+            }
+            ChannelType::Noise => {
+                if let Some(note) = self.channel4.active_note {
+                    self.channel4.frequency = Self::midi_note_to_gb_frequency(note, semitones);
+                }
+            }
+        }
+    }
+
+    /// Set the duty cycle (0-3, see `SquareChannel::get_sample`'s
+    /// `duty_patterns`) of a square channel. No-op for wave/noise.
+    pub fn set_duty_cycle(&mut self, channel: ChannelType, duty_cycle: u8) {
+        match channel {
+            ChannelType::Square1 => self.channel1.duty_cycle = duty_cycle & 0x03,
+            ChannelType::Square2 => self.channel2.duty_cycle = duty_cycle & 0x03,
+            _ => {}
+        }
+    }
+
+    /// Set the volume envelope period (0 disables it) and direction
+    /// (`true` = increase) for a square or noise channel.
+    pub fn set_envelope(&mut self, channel: ChannelType, period: u8, direction: bool) {
+        match channel {
+            ChannelType::Square1 => {
+                self.channel1.envelope_period = period;
+                self.channel1.envelope_direction = direction;
+            }
+            ChannelType::Square2 => {
+                self.channel2.envelope_period = period;
+                self.channel2.envelope_direction = direction;
+            }
+            ChannelType::Noise => {
+                self.channel4.envelope_period = period;
+                self.channel4.envelope_direction = direction;
+            }
+            ChannelType::Wave => {}
+        }
+    }
+
+    /// Set channel 1's frequency sweep (period, direction where `true` =
+    /// increase, and shift). Real hardware only wires sweep to channel 1.
+    pub fn set_sweep(&mut self, period: u8, direction: bool, shift: u8) {
+        self.channel1.sweep_period = period;
+        self.channel1.sweep_direction = direction;
+        self.channel1.sweep_shift = shift;
+    }
+}
+
 impl Default for Apu {
     fn default() -> Self {
         Self::new()
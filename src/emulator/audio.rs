@@ -0,0 +1,134 @@
+//! Lock-free audio output interface
+//!
+//! Decouples the APU's sample production from however the platform layer
+//! consumes it. The APU holds the producer half of a single-producer
+//! single-consumer ring buffer and pushes stereo frames as it generates
+//! them; platform code (a native `cpal` callback, a WASM `AudioWorklet`)
+//! owns the consumer half and pulls exactly what the sound card needs on
+//! its own schedule. This removes the old fixed `[i16; 1024]` buffer and
+//! its wrapping `buffer_pos`, which silently aliased samples whenever the
+//! consumer drained at a different rate than samples were produced.
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Default ring buffer capacity, in stereo frames (~93ms at 44.1kHz).
+pub const DEFAULT_CAPACITY: usize = 4096;
+
+/// One stereo audio frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StereoFrame {
+    pub left: i16,
+    pub right: i16,
+}
+
+impl StereoFrame {
+    pub fn new(left: i16, right: i16) -> Self {
+        Self { left, right }
+    }
+}
+
+/// Sink that the APU pushes rendered frames into. `NullAudio` is the
+/// headless/benchmark implementation: it accepts and discards frames so
+/// `criterion` runs don't allocate or synchronize with a consumer.
+pub trait AudioInterface {
+    fn push_frame(&mut self, frame: StereoFrame);
+}
+
+/// An `AudioInterface` that drops every frame it's given.
+pub struct NullAudio;
+
+impl AudioInterface for NullAudio {
+    fn push_frame(&mut self, _frame: StereoFrame) {}
+}
+
+/// Shared ring buffer storage. One slot of `capacity` is always kept empty
+/// so `head == tail` is unambiguous as "empty" without a separate counter.
+struct RingBuffer {
+    data: Box<[UnsafeCell<StereoFrame>]>,
+    capacity: usize,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+// SAFETY: `data` is only ever written through the single producer (at
+// `head`) and read through the single consumer (at `tail`); the atomics
+// establish the happens-before edges between them.
+unsafe impl Sync for RingBuffer {}
+
+/// Producer half of the ring buffer. Lives on the APU.
+pub struct AudioProducer {
+    ring: Arc<RingBuffer>,
+}
+
+/// Consumer half of the ring buffer. Lives on the platform audio callback.
+pub struct AudioConsumer {
+    ring: Arc<RingBuffer>,
+}
+
+/// Create a ring buffer with room for `capacity` stereo frames.
+pub fn ring_buffer(capacity: usize) -> (AudioProducer, AudioConsumer) {
+    let slots = capacity + 1;
+    let data = (0..slots)
+        .map(|_| UnsafeCell::new(StereoFrame::default()))
+        .collect();
+    let ring = Arc::new(RingBuffer {
+        data,
+        capacity: slots,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+    });
+    (
+        AudioProducer { ring: ring.clone() },
+        AudioConsumer { ring },
+    )
+}
+
+impl AudioProducer {
+    /// Push one frame. Returns `false` (dropping the frame) if the
+    /// consumer hasn't drained the buffer in time, so the emulation
+    /// thread never blocks waiting on the audio callback.
+    pub fn push(&mut self, frame: StereoFrame) -> bool {
+        let head = self.ring.head.load(Ordering::Relaxed);
+        let next = (head + 1) % self.ring.capacity;
+        if next == self.ring.tail.load(Ordering::Acquire) {
+            return false;
+        }
+        unsafe {
+            *self.ring.data[head].get() = frame;
+        }
+        self.ring.head.store(next, Ordering::Release);
+        true
+    }
+}
+
+impl AudioInterface for AudioProducer {
+    fn push_frame(&mut self, frame: StereoFrame) {
+        let _ = self.push(frame);
+    }
+}
+
+impl AudioConsumer {
+    /// Pop the oldest unread frame, or `None` if the buffer is empty.
+    pub fn pop(&mut self) -> Option<StereoFrame> {
+        let tail = self.ring.tail.load(Ordering::Relaxed);
+        if tail == self.ring.head.load(Ordering::Acquire) {
+            return None;
+        }
+        let frame = unsafe { *self.ring.data[tail].get() };
+        self.ring.tail.store((tail + 1) % self.ring.capacity, Ordering::Release);
+        Some(frame)
+    }
+
+    /// Number of frames currently available to read.
+    pub fn len(&self) -> usize {
+        let head = self.ring.head.load(Ordering::Acquire);
+        let tail = self.ring.tail.load(Ordering::Acquire);
+        (head + self.ring.capacity - tail) % self.ring.capacity
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
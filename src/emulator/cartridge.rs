@@ -2,8 +2,602 @@
 //!
 //! Handles different cartridge types and memory bank switching.
 
+use std::path::Path;
+use std::time::SystemTime;
+
 use crate::EmulatorError;
 
+/// MBC3 real-time clock. Backed by a stored `SystemTime` plus accumulated
+/// seconds rather than a counter ticked per CPU cycle, so the clock keeps
+/// real time even while the emulator isn't running. `0xA000..=0xBFFF`
+/// reads/writes only ever touch the latched registers (`latched_*`); a
+/// `0x00` then `0x01` write to `0x6000..=0x7FFF` copies the live clock into
+/// them.
+struct RtcClock {
+    /// Seconds accumulated while halted, or as of `running_since` while
+    /// running.
+    accumulated_seconds: u64,
+    /// `None` while halted (bit 6 of day-high is set).
+    running_since: Option<SystemTime>,
+    latched_seconds: u8,
+    latched_minutes: u8,
+    latched_hours: u8,
+    latched_day_low: u8,
+    /// Bit 0: day counter bit 8. Bit 6: halt. Bit 7: day carry.
+    latched_day_high: u8,
+    /// Set by a `0x00` write to the latch range, waiting for the `0x01`
+    /// that completes the sequence.
+    latch_armed: bool,
+}
+
+impl RtcClock {
+    fn new() -> Self {
+        Self {
+            accumulated_seconds: 0,
+            running_since: Some(SystemTime::now()),
+            latched_seconds: 0,
+            latched_minutes: 0,
+            latched_hours: 0,
+            latched_day_low: 0,
+            latched_day_high: 0,
+            latch_armed: false,
+        }
+    }
+
+    fn halted(&self) -> bool {
+        self.running_since.is_none()
+    }
+
+    fn current_seconds(&self) -> u64 {
+        let running = self
+            .running_since
+            .and_then(|t| t.elapsed().ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.accumulated_seconds + running
+    }
+
+    fn set_halted(&mut self, halt: bool) {
+        match (halt, self.running_since) {
+            (true, Some(_)) => {
+                self.accumulated_seconds = self.current_seconds();
+                self.running_since = None;
+            }
+            (false, None) => {
+                self.running_since = Some(SystemTime::now());
+            }
+            _ => {}
+        }
+    }
+
+    /// Copy the live clock into the latched registers.
+    fn latch(&mut self) {
+        let total = self.current_seconds();
+        let days = total / 86_400;
+        self.latched_seconds = (total % 60) as u8;
+        self.latched_minutes = ((total / 60) % 60) as u8;
+        self.latched_hours = ((total / 3_600) % 24) as u8;
+        self.latched_day_low = (days & 0xFF) as u8;
+        let day_high_bit = ((days >> 8) & 0x01) as u8;
+        let halt_bit = if self.halted() { 0x40 } else { 0x00 };
+        let carry_bit = if days > 511 { 0x80 } else { 0x00 };
+        self.latched_day_high = day_high_bit | halt_bit | carry_bit;
+    }
+
+    /// Handle a write to the `0x6000..=0x7FFF` latch range.
+    fn handle_latch_write(&mut self, value: u8) {
+        if value == 0x00 {
+            self.latch_armed = true;
+        } else if value == 0x01 && self.latch_armed {
+            self.latch();
+            self.latch_armed = false;
+        } else {
+            self.latch_armed = false;
+        }
+    }
+
+    fn read_register(&self, register: u8) -> u8 {
+        match register {
+            0x08 => self.latched_seconds,
+            0x09 => self.latched_minutes,
+            0x0A => self.latched_hours,
+            0x0B => self.latched_day_low,
+            0x0C => self.latched_day_high,
+            _ => 0xFF,
+        }
+    }
+
+    /// Writing an RTC register sets the clock directly (how games set the
+    /// time for the first boot); recompute the live base from the edited
+    /// latch so the change takes effect immediately.
+    fn write_register(&mut self, register: u8, value: u8) {
+        match register {
+            0x08 => self.latched_seconds = value % 60,
+            0x09 => self.latched_minutes = value % 60,
+            0x0A => self.latched_hours = value % 24,
+            0x0B => self.latched_day_low = value,
+            0x0C => {
+                let was_halted = self.halted();
+                self.latched_day_high = value & 0xC1;
+                let now_halted = value & 0x40 != 0;
+                if now_halted != was_halted {
+                    self.set_halted(now_halted);
+                }
+            }
+            _ => return,
+        }
+
+        let days = (((self.latched_day_high & 0x01) as u64) << 8) | self.latched_day_low as u64;
+        self.accumulated_seconds = days * 86_400
+            + self.latched_hours as u64 * 3_600
+            + self.latched_minutes as u64 * 60
+            + self.latched_seconds as u64;
+        if !self.halted() {
+            self.running_since = Some(SystemTime::now());
+        }
+    }
+
+    /// Serialize the live seconds count, the latched registers, and the
+    /// halt/latch-armed flags, so a `.sav` file can restore the clock
+    /// exactly as it was (wall-clock progression resumes from there).
+    fn save_state(&self) -> [u8; RTC_STATE_SIZE] {
+        let mut buf = [0u8; RTC_STATE_SIZE];
+        buf[0..8].copy_from_slice(&self.current_seconds().to_le_bytes());
+        buf[8] = self.latched_seconds;
+        buf[9] = self.latched_minutes;
+        buf[10] = self.latched_hours;
+        buf[11] = self.latched_day_low;
+        buf[12] = self.latched_day_high;
+        buf[13] = self.halted() as u8;
+        buf[14] = self.latch_armed as u8;
+        buf
+    }
+
+    /// Restore state produced by `save_state`.
+    fn load_state(&mut self, data: &[u8; RTC_STATE_SIZE]) {
+        self.accumulated_seconds = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        self.latched_seconds = data[8];
+        self.latched_minutes = data[9];
+        self.latched_hours = data[10];
+        self.latched_day_low = data[11];
+        self.latched_day_high = data[12];
+        self.running_since = if data[13] != 0 {
+            None
+        } else {
+            Some(SystemTime::now())
+        };
+        self.latch_armed = data[14] != 0;
+    }
+}
+
+/// Bytes needed by `RtcClock::save_state`: an 8-byte seconds count plus the
+/// five latched registers plus the halted and latch-armed flags.
+const RTC_STATE_SIZE: usize = 8 + 5 + 1 + 1;
+
+/// Bytes `battery_state`/`load_battery_state` spend on the banking
+/// registers shared by every MBC type: a 2-byte `current_rom_bank`, plus
+/// one byte each for `current_ram_bank`, `ram_enabled`, `banking_mode`,
+/// `rtc_register_select` (`0xFF` encodes `None`), and `huc3_mode`. Without
+/// these a restored snapshot would keep whatever bank happened to be
+/// selected at load time instead of the one active when it was saved.
+const BANK_STATE_SIZE: usize = 2 + 1 + 1 + 1 + 1 + 1;
+
+/// MBC7's tilt sensor, latched into two 16-bit values centered at `0x81D0`.
+/// A `0x55` then `0xAA` write to the latch register snapshots the current
+/// host tilt so reads stay stable mid-frame, mirroring the RTC's own
+/// `0x00`-then-`0x01` latch sequence above.
+struct Mbc7Accelerometer {
+    tilt_x: f32,
+    tilt_y: f32,
+    latched_x: u16,
+    latched_y: u16,
+    latch_armed: bool,
+}
+
+impl Mbc7Accelerometer {
+    const CENTER: i32 = 0x81D0;
+    /// Counts per full `-1.0..=1.0` tilt; real hardware reports on this
+    /// order of magnitude per g of lateral acceleration.
+    const SCALE: f32 = 0x70 as f32;
+
+    fn new() -> Self {
+        let center = Self::CENTER as u16;
+        Self {
+            tilt_x: 0.0,
+            tilt_y: 0.0,
+            latched_x: center,
+            latched_y: center,
+            latch_armed: false,
+        }
+    }
+
+    fn set_tilt(&mut self, x: f32, y: f32) {
+        self.tilt_x = x.clamp(-1.0, 1.0);
+        self.tilt_y = y.clamp(-1.0, 1.0);
+    }
+
+    fn axis_value(tilt: f32) -> u16 {
+        (Self::CENTER + (tilt * Self::SCALE) as i32).clamp(0, 0xFFFF) as u16
+    }
+
+    /// Handle a write to the latch register.
+    fn handle_latch_write(&mut self, value: u8) {
+        if value == 0x55 {
+            self.latch_armed = true;
+        } else if value == 0xAA && self.latch_armed {
+            self.latched_x = Self::axis_value(self.tilt_x);
+            self.latched_y = Self::axis_value(self.tilt_y);
+            self.latch_armed = false;
+        } else {
+            self.latch_armed = false;
+        }
+    }
+}
+
+/// One in-flight 93LC56 transaction: the protocol is 1 start bit + 2
+/// opcode bits + 7 address bits, then a 16-bit data phase for READ/WRITE.
+#[derive(Clone, Copy)]
+enum EepromState {
+    /// Shifting in the start bit, opcode, and address, MSB first. `bits`
+    /// only holds real protocol bits — leading zeros before the start bit
+    /// are dropped rather than counted.
+    Command { bits: u16, count: u8 },
+    /// Shifting the addressed word out on DO, MSB first.
+    Read { word: u16, remaining: u8 },
+    /// Shifting 16 data bits in on DI before committing a write.
+    Write { address: usize, bits: u16, remaining: u8 },
+}
+
+/// MBC7's bit-banged 93LC56 serial EEPROM: 128 16-bit words (256 bytes),
+/// addressed with the standard 93Cxx protocol. Only the opcodes MBC7 games
+/// actually issue are decoded — READ, WRITE, and the write-enable/disable
+/// extended commands (EWEN/EWDS); ERASE and the ALL variants are accepted
+/// (so the bit stream still advances correctly) but have no effect.
+struct Mbc7Eeprom {
+    data: [u16; 128],
+    write_enabled: bool,
+    chip_select: bool,
+    clock: bool,
+    state: EepromState,
+}
+
+impl Mbc7Eeprom {
+    fn new() -> Self {
+        Self {
+            data: [0xFFFF; 128],
+            write_enabled: false,
+            chip_select: false,
+            clock: false,
+            state: EepromState::Command { bits: 0, count: 0 },
+        }
+    }
+
+    /// Handle a write to the bit-banged control register: bit 7 is CS, bit
+    /// 1 is CLK, bit 0 is DI. Bits shift in on the CLK rising edge while CS
+    /// is held high; dropping CS aborts whatever transaction is in flight.
+    fn write_control(&mut self, value: u8) {
+        let cs = value & 0x80 != 0;
+        let clk = value & 0x02 != 0;
+        let di = value & 0x01 != 0;
+
+        if !cs {
+            self.chip_select = false;
+            self.clock = clk;
+            self.state = EepromState::Command { bits: 0, count: 0 };
+            return;
+        }
+        if !self.chip_select {
+            self.state = EepromState::Command { bits: 0, count: 0 };
+        }
+        self.chip_select = true;
+
+        let rising_edge = clk && !self.clock;
+        self.clock = clk;
+        if rising_edge {
+            self.clock_in(di);
+        }
+    }
+
+    fn clock_in(&mut self, di: bool) {
+        match self.state {
+            EepromState::Command { bits, count } => {
+                if count == 0 && !di {
+                    return; // Waiting for the start bit.
+                }
+                let bits = (bits << 1) | di as u16;
+                let count = count + 1;
+                if count < 10 {
+                    self.state = EepromState::Command { bits, count };
+                    return;
+                }
+
+                let opcode = (bits >> 7) & 0x03;
+                let address = (bits & 0x7F) as usize;
+                self.state = match opcode {
+                    0b10 => EepromState::Read {
+                        word: self.data[address],
+                        remaining: 16,
+                    },
+                    0b01 => EepromState::Write {
+                        address,
+                        bits: 0,
+                        remaining: 16,
+                    },
+                    0b00 => {
+                        match (address >> 5) & 0x03 {
+                            0b11 => self.write_enabled = true,  // EWEN
+                            0b00 => self.write_enabled = false, // EWDS
+                            _ => {}                             // WRAL/ERAL: unimplemented
+                        }
+                        EepromState::Command { bits: 0, count: 0 }
+                    }
+                    _ => EepromState::Command { bits: 0, count: 0 }, // ERASE: unimplemented
+                };
+            }
+            EepromState::Read { word, remaining } => {
+                if remaining > 0 {
+                    self.state = EepromState::Read {
+                        word: word << 1,
+                        remaining: remaining - 1,
+                    };
+                }
+            }
+            EepromState::Write {
+                address,
+                bits,
+                remaining,
+            } => {
+                if remaining == 0 {
+                    return;
+                }
+                let bits = (bits << 1) | di as u16;
+                let remaining = remaining - 1;
+                self.state = if remaining == 0 {
+                    if self.write_enabled {
+                        self.data[address] = bits;
+                    }
+                    EepromState::Command { bits: 0, count: 0 }
+                } else {
+                    EepromState::Write {
+                        address,
+                        bits,
+                        remaining,
+                    }
+                };
+            }
+        }
+    }
+
+    /// Current DO line value, read back through the control register.
+    fn read_do(&self) -> bool {
+        match self.state {
+            EepromState::Read { word, remaining } if remaining > 0 => (word >> 15) & 1 != 0,
+            _ => false,
+        }
+    }
+
+    fn save_state(&self) -> [u8; MBC7_EEPROM_STATE_SIZE] {
+        let mut buf = [0u8; MBC7_EEPROM_STATE_SIZE];
+        for (i, word) in self.data.iter().enumerate() {
+            buf[i * 2..i * 2 + 2].copy_from_slice(&word.to_le_bytes());
+        }
+        buf
+    }
+
+    fn load_state(&mut self, data: &[u8; MBC7_EEPROM_STATE_SIZE]) {
+        for (i, word) in self.data.iter_mut().enumerate() {
+            *word = u16::from_le_bytes([data[i * 2], data[i * 2 + 1]]);
+        }
+    }
+}
+
+/// 128 16-bit words, serialized little-endian.
+const MBC7_EEPROM_STATE_SIZE: usize = 128 * 2;
+
+/// MBC7 accelerometer + EEPROM state, held together since both live behind
+/// the same `0xA000..=0xBFFF` register window.
+struct Mbc7State {
+    accelerometer: Mbc7Accelerometer,
+    eeprom: Mbc7Eeprom,
+}
+
+impl Mbc7State {
+    fn new() -> Self {
+        Self {
+            accelerometer: Mbc7Accelerometer::new(),
+            eeprom: Mbc7Eeprom::new(),
+        }
+    }
+}
+
+/// HuC3's real-time clock, accessed through a simplified command/response
+/// protocol on `0xA000..=0xBFFF` (routed here instead of to RAM while the
+/// register-select value written to `0x0000..=0x1FFF` is `0x0B`) rather
+/// than MBC3's latched registers. Real hardware shifts the time in and out
+/// nibble-by-nibble across several command bytes; this models just enough
+/// of that protocol for games to read and set the clock.
+struct Huc3Rtc {
+    accumulated_seconds: u64,
+    running_since: Option<SystemTime>,
+    /// Nibbles of the current time, queued by a `0x1x` "read" command and
+    /// drained one per subsequent read.
+    read_queue: Vec<u8>,
+    /// Nibbles accumulated by `0x3x` "write" commands, committed to
+    /// `accumulated_seconds` by a `0x4x` command.
+    write_accumulator: u64,
+}
+
+impl Huc3Rtc {
+    fn new() -> Self {
+        Self {
+            accumulated_seconds: 0,
+            running_since: Some(SystemTime::now()),
+            read_queue: Vec::new(),
+            write_accumulator: 0,
+        }
+    }
+
+    fn current_seconds(&self) -> u64 {
+        let running = self
+            .running_since
+            .and_then(|t| t.elapsed().ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.accumulated_seconds + running
+    }
+
+    /// Handle a command byte written to `0xA000..=0xBFFF` while in RTC mode.
+    fn write_command(&mut self, value: u8) {
+        match value >> 4 {
+            0x1 => {
+                // Queue the current clock's nibbles, least-significant first.
+                let mut seconds = self.current_seconds();
+                self.read_queue.clear();
+                for _ in 0..8 {
+                    self.read_queue.push((seconds & 0xF) as u8);
+                    seconds >>= 4;
+                }
+            }
+            0x3 => {
+                self.write_accumulator = (self.write_accumulator << 4) | (value & 0xF) as u64;
+            }
+            0x4 => {
+                self.accumulated_seconds = self.write_accumulator;
+                self.write_accumulator = 0;
+                self.running_since = Some(SystemTime::now());
+            }
+            _ => {}
+        }
+    }
+
+    /// Next output nibble for a read of `0xA000..=0xBFFF` while in RTC
+    /// mode, `0x80`-flagged to mark the response ready (mirroring real
+    /// hardware's "chip answer ready" bit), or a bare `0x01` idle status
+    /// once the queue has drained.
+    fn read_response(&mut self) -> u8 {
+        if self.read_queue.is_empty() {
+            0x01
+        } else {
+            0x80 | self.read_queue.remove(0)
+        }
+    }
+
+    fn save_state(&self) -> [u8; 8] {
+        self.current_seconds().to_le_bytes()
+    }
+
+    fn load_state(&mut self, data: &[u8; 8]) {
+        self.accumulated_seconds = u64::from_le_bytes(*data);
+        self.running_since = Some(SystemTime::now());
+    }
+}
+
+/// Native Pocket Camera sensor resolution.
+const CAM_WIDTH: usize = 128;
+const CAM_HEIGHT: usize = 112;
+
+/// 0x36-byte sensor register block mapped to `0xA000..=0xA035` while RAM
+/// bank `0x10` is selected.
+const CAM_REGISTER_COUNT: usize = 0x36;
+
+/// Developed photo, as 2bpp Game Boy tiles: 16x14 tiles of 16 bytes each,
+/// mirrored at `0xA100` while RAM bank `0x10` is selected.
+const CAM_TILE_DATA_LEN: usize = (CAM_WIDTH / 8) * (CAM_HEIGHT / 8) * 16;
+const CAM_TILE_DATA_OFFSET: usize = 0x100;
+
+/// How many register-0 reads report a capture as still in progress before
+/// it completes. Real hardware's capture takes a fixed number of PPU
+/// frames; we don't model that timing, so this just gives ROMs that poll
+/// the busy bit a few reads to see before it clears.
+const CAM_CAPTURE_BUSY_READS: u8 = 8;
+
+/// Pocket Camera (Game Boy Camera / M64282FP) sensor state: the register
+/// block games configure exposure/contrast/dithering through, the last
+/// frame `set_camera_frame` handed us, and the tiles developed from it.
+struct CameraSensor {
+    registers: [u8; CAM_REGISTER_COUNT],
+    capture_busy_reads: u8,
+    pending_frame: Vec<u8>,
+    tile_data: Vec<u8>,
+}
+
+impl CameraSensor {
+    fn new() -> Self {
+        Self {
+            registers: [0; CAM_REGISTER_COUNT],
+            capture_busy_reads: 0,
+            pending_frame: vec![128; CAM_WIDTH * CAM_HEIGHT],
+            tile_data: vec![0; CAM_TILE_DATA_LEN],
+        }
+    }
+
+    /// Accept a host-supplied grayscale frame (one byte per pixel, native
+    /// 128x112). Mismatched sizes are ignored rather than panicking, since
+    /// a frontend between capture devices may briefly hand us the wrong
+    /// shape.
+    fn set_frame(&mut self, frame: &[u8]) {
+        if frame.len() == self.pending_frame.len() {
+            self.pending_frame.copy_from_slice(frame);
+        }
+    }
+
+    fn read_register(&mut self, offset: usize) -> u8 {
+        if offset == 0 {
+            if self.capture_busy_reads > 0 {
+                self.capture_busy_reads -= 1;
+                self.registers[0] | 0x01
+            } else {
+                self.registers[0] & !0x01
+            }
+        } else {
+            self.registers[offset]
+        }
+    }
+
+    fn write_register(&mut self, offset: usize, value: u8) {
+        self.registers[offset] = value;
+        if offset == 0 && value & 0x01 != 0 {
+            self.develop();
+            self.capture_busy_reads = CAM_CAPTURE_BUSY_READS;
+        }
+    }
+
+    fn read_tile_data(&self, index: usize) -> u8 {
+        self.tile_data.get(index).copied().unwrap_or(0xFF)
+    }
+
+    /// Run the pending frame through the sensor's exposure, contrast, and
+    /// ordered-dither registers to produce 2bpp tiles, as real hardware
+    /// does when a capture completes.
+    fn develop(&mut self) {
+        let exposure = (((self.registers[1] as u16) << 8) | self.registers[2] as u16).max(1);
+        let contrast_factor = self.registers[3] as f32 / 128.0;
+        let dither = &self.registers[6..6 + 16];
+
+        self.tile_data.fill(0);
+        let tiles_per_row = CAM_WIDTH / 8;
+        for y in 0..CAM_HEIGHT {
+            for x in 0..CAM_WIDTH {
+                let raw = self.pending_frame[y * CAM_WIDTH + x] as f32;
+                let exposed = (raw * exposure as f32 / 0x0F00 as f32).clamp(0.0, 255.0);
+                let contrasted = ((exposed - 128.0) * contrast_factor + 128.0).clamp(0.0, 255.0);
+                let threshold = dither[(y % 4) * 4 + (x % 4)] as f32 - 128.0;
+                let dithered = (contrasted + threshold * 0.25).clamp(0.0, 255.0);
+                // 0 = lightest, 3 = darkest, matching the DMG tile convention.
+                let shade = 3 - ((dithered as u32 * 4) / 256).min(3);
+
+                let tile_col = x / 8;
+                let tile_row = y / 8;
+                let tile_index = tile_row * tiles_per_row + tile_col;
+                let tile_offset = tile_index * 16 + (y % 8) * 2;
+                let bit = 7 - (x % 8);
+                self.tile_data[tile_offset] |= ((shade & 0x01) as u8) << bit;
+                self.tile_data[tile_offset + 1] |= (((shade >> 1) & 0x01) as u8) << bit;
+            }
+        }
+    }
+}
+
 /// Cartridge header information
 #[derive(Debug, Clone)]
 pub struct CartridgeHeader {
@@ -26,6 +620,10 @@ pub enum MbcType {
     Mbc2,
     Mbc3,
     Mbc5,
+    Mbc7,
+    HuC1,
+    HuC3,
+    PocketCamera,
     Unsupported(u8),
 }
 
@@ -41,11 +639,32 @@ pub struct Cartridge {
     pub current_ram_bank: usize,
     pub ram_enabled: bool,
     pub banking_mode: u8,
+
+    // MBC3 RTC state: `Some(register)` while `0x4000..=0x5FFF` has selected
+    // an RTC register (0x08-0x0C) instead of a RAM bank.
+    rtc_register_select: Option<u8>,
+    rtc: RtcClock,
+
+    // MBC7 accelerometer + EEPROM state; `None` for every other MBC type.
+    mbc7: Option<Mbc7State>,
+
+    // HuC3 register-select value written to `0x0000..=0x1FFF`: `0x0A` for
+    // RAM, `0x0B` for the RTC, `0x0D` for the (always-no-signal) IR port.
+    huc3_mode: u8,
+    huc3_rtc: Huc3Rtc,
+
+    // Pocket Camera sensor registers/frame/tiles; `None` for every other
+    // cartridge type. Mapped into `0xA000..=0xBFFF` when RAM bank `0x10`
+    // is selected.
+    camera: Option<CameraSensor>,
 }
 
 impl Cartridge {
-    /// Create a new cartridge from ROM data
-    pub fn new(rom_data: Vec<u8>) -> Result<Self, EmulatorError> {
+    /// Create a new cartridge from ROM data. If the cartridge has a battery
+    /// and `save_path` points at an existing file, that file is loaded with
+    /// `load_save` so battery-backed saves survive across emulator runs; a
+    /// file whose size doesn't match makes `new` return that error.
+    pub fn new(rom_data: Vec<u8>, save_path: Option<&Path>) -> Result<Self, EmulatorError> {
         if rom_data.len() < 0x8000 {
             return Err(EmulatorError::InvalidRom);
         }
@@ -53,8 +672,8 @@ impl Cartridge {
         let header = Self::parse_header(&rom_data)?;
         let mbc_type = Self::determine_mbc_type(header.cartridge_type)?;
         let ram_size = Self::get_ram_size(header.ram_size);
-        
-        Ok(Self {
+
+        let mut cartridge = Self {
             header,
             mbc_type,
             rom_data,
@@ -63,7 +682,172 @@ impl Cartridge {
             current_ram_bank: 0,
             ram_enabled: false,
             banking_mode: 0,
-        })
+            rtc_register_select: None,
+            rtc: RtcClock::new(),
+            mbc7: if mbc_type == MbcType::Mbc7 {
+                Some(Mbc7State::new())
+            } else {
+                None
+            },
+            huc3_mode: 0,
+            huc3_rtc: Huc3Rtc::new(),
+            camera: if mbc_type == MbcType::PocketCamera {
+                Some(CameraSensor::new())
+            } else {
+                None
+            },
+        };
+
+        if let Some(path) = save_path {
+            if cartridge.has_battery() && path.exists() {
+                cartridge.load_save(path)?;
+            }
+        }
+
+        Ok(cartridge)
+    }
+
+    /// Whether this cartridge type backs its external RAM with a battery,
+    /// i.e. whether it's worth persisting `ram_data` to a `.sav` file.
+    pub fn has_battery(&self) -> bool {
+        matches!(
+            self.header.cartridge_type,
+            0x03 | 0x06 | 0x0F | 0x10 | 0x13 | 0x1B | 0x1E | 0x22 | 0xFC | 0xFE | 0xFF
+        )
+    }
+
+    /// Whether this cartridge type has an MBC3 real-time clock whose state
+    /// should be persisted alongside the save RAM.
+    fn has_rtc(&self) -> bool {
+        matches!(self.header.cartridge_type, 0x0F | 0x10)
+    }
+
+    /// Whether this cartridge type has a HuC3 real-time clock whose state
+    /// should be persisted alongside the save RAM.
+    fn has_huc3_rtc(&self) -> bool {
+        self.mbc_type == MbcType::HuC3
+    }
+
+    /// Feed host tilt input to an MBC7 cartridge's accelerometer. A no-op
+    /// on every other cartridge type.
+    pub fn set_tilt(&mut self, x: f32, y: f32) {
+        if let Some(mbc7) = &mut self.mbc7 {
+            mbc7.accelerometer.set_tilt(x, y);
+        }
+    }
+
+    /// Feed a host-captured grayscale frame (one byte per pixel, native
+    /// 128x112) to a Pocket Camera cartridge's sensor. A no-op on every
+    /// other cartridge type. Takes effect the next time the ROM triggers a
+    /// capture.
+    pub fn set_camera_frame(&mut self, frame: &[u8]) {
+        if let Some(camera) = &mut self.camera {
+            camera.set_frame(frame);
+        }
+    }
+
+    /// Load external RAM, the banking registers, and (for MBC3+TIMER/MBC7/
+    /// HuC3 cartridges) the RTC or EEPROM state from a `.sav` file, replacing
+    /// `ram_data`. Fails if the file's size doesn't match what `save` writes
+    /// for this cartridge, since that almost always means it belongs to a
+    /// different ROM.
+    pub fn load_save(&mut self, path: &Path) -> Result<(), EmulatorError> {
+        let data = std::fs::read(path)
+            .map_err(|e| EmulatorError::MemoryError(format!("failed to read save file: {e}")))?;
+
+        let expected_len = self.battery_state_len();
+        if data.len() != expected_len {
+            return Err(EmulatorError::MemoryError(format!(
+                "save file size {} doesn't match expected size {}",
+                data.len(),
+                expected_len
+            )));
+        }
+
+        self.load_battery_state(&data);
+        Ok(())
+    }
+
+    /// Flush external RAM (and, for MBC3+TIMER/MBC7/HuC3 cartridges, the
+    /// RTC or EEPROM state) to a `.sav` file. Callers should do this on
+    /// clean shutdown (and ideally whenever RAM is disabled) for
+    /// battery-backed cartridges.
+    pub fn save(&self, path: &Path) -> Result<(), EmulatorError> {
+        std::fs::write(path, self.battery_state())
+            .map_err(|e| EmulatorError::MemoryError(format!("failed to write save file: {e}")))
+    }
+
+    /// Serialize external RAM plus whatever RTC/EEPROM state this cartridge
+    /// type carries, for a machine save state. Shared by the `.sav`-file
+    /// path above and `Mmu`'s `save_state`, so a save state's cartridge
+    /// portion is exactly a `.sav` file's bytes.
+    pub fn save_state(&self) -> Vec<u8> {
+        self.battery_state()
+    }
+
+    /// Restore state previously produced by `save_state`.
+    pub fn load_state(&mut self, data: &[u8]) {
+        self.load_battery_state(data);
+    }
+
+    /// Length in bytes of `battery_state()`'s output for this cartridge.
+    fn battery_state_len(&self) -> usize {
+        self.ram_data.len()
+            + BANK_STATE_SIZE
+            + if self.has_rtc() { RTC_STATE_SIZE } else { 0 }
+            + if self.mbc7.is_some() {
+                MBC7_EEPROM_STATE_SIZE
+            } else {
+                0
+            }
+            + if self.has_huc3_rtc() { 8 } else { 0 }
+    }
+
+    fn battery_state(&self) -> Vec<u8> {
+        let mut data = self.ram_data.clone();
+        data.extend_from_slice(&(self.current_rom_bank as u16).to_le_bytes());
+        data.push(self.current_ram_bank as u8);
+        data.push(self.ram_enabled as u8);
+        data.push(self.banking_mode);
+        data.push(self.rtc_register_select.unwrap_or(0xFF));
+        data.push(self.huc3_mode);
+        if self.has_rtc() {
+            data.extend_from_slice(&self.rtc.save_state());
+        }
+        if let Some(mbc7) = &self.mbc7 {
+            data.extend_from_slice(&mbc7.eeprom.save_state());
+        }
+        if self.has_huc3_rtc() {
+            data.extend_from_slice(&self.huc3_rtc.save_state());
+        }
+        data
+    }
+
+    fn load_battery_state(&mut self, data: &[u8]) {
+        let (ram, rest) = data.split_at(self.ram_data.len());
+        self.ram_data.copy_from_slice(ram);
+
+        let (bank_state, rest) = rest.split_at(BANK_STATE_SIZE);
+        self.current_rom_bank = u16::from_le_bytes([bank_state[0], bank_state[1]]) as usize;
+        self.current_ram_bank = bank_state[2] as usize;
+        self.ram_enabled = bank_state[3] != 0;
+        self.banking_mode = bank_state[4];
+        self.rtc_register_select = if bank_state[5] == 0xFF {
+            None
+        } else {
+            Some(bank_state[5])
+        };
+        self.huc3_mode = bank_state[6];
+
+        if self.has_rtc() {
+            self.rtc.load_state(rest.try_into().unwrap());
+        }
+        if let Some(mbc7) = &mut self.mbc7 {
+            mbc7.eeprom.load_state(rest.try_into().unwrap());
+        }
+        if self.has_huc3_rtc() {
+            self.huc3_rtc.load_state(rest.try_into().unwrap());
+        }
     }
 
     /// Parse the cartridge header
@@ -110,6 +894,10 @@ impl Cartridge {
             0x05..=0x06 => Ok(MbcType::Mbc2),
             0x0F..=0x13 => Ok(MbcType::Mbc3),
             0x19..=0x1E => Ok(MbcType::Mbc5),
+            0x22 => Ok(MbcType::Mbc7),
+            0xFC => Ok(MbcType::PocketCamera),
+            0xFE => Ok(MbcType::HuC3),
+            0xFF => Ok(MbcType::HuC1),
             _ => Err(EmulatorError::UnsupportedCartridge(cartridge_type)),
         }
     }
@@ -127,8 +915,35 @@ impl Cartridge {
         }
     }
 
-    /// Read from cartridge memory space
-    pub fn read(&self, address: u16) -> u8 {
+    /// Number of 16KB ROM banks actually present, from `header.rom_size`
+    /// (`32KB << code`). Always a power of two, so masking a selected bank
+    /// with `count - 1` wraps it into range instead of over-reading.
+    fn rom_bank_count(&self) -> usize {
+        ((32 * 1024) << self.header.rom_size) / 0x4000
+    }
+
+    /// Index into `rom_data` for `current_rom_bank`, wrapped to the ROM's
+    /// real bank count so a bank register wider than the actual ROM (common
+    /// on carts whose true size isn't a clean power of two relative to the
+    /// register width) doesn't read out of bounds.
+    fn masked_rom_bank(&self) -> usize {
+        (self.current_rom_bank & (self.rom_bank_count() - 1)) % (self.rom_data.len() / 0x4000)
+    }
+
+    /// Index into `ram_data` for `(current_ram_bank, address)`, mirrored by
+    /// the actual RAM length. This both masks the bank number against
+    /// whatever banks really exist and, for carts with less than a full
+    /// 8KB (e.g. the 2KB MBC1 RAM), mirrors accesses within the window
+    /// instead of assuming a full-size bank.
+    fn ram_address(&self, address: u16) -> usize {
+        let bank_offset = self.current_ram_bank * 0x2000;
+        let raw = bank_offset + (address - 0xA000) as usize;
+        raw % self.ram_data.len()
+    }
+
+    /// Read from cartridge memory space. `&mut self` because HuC3's RTC
+    /// command queue is drained one nibble per read.
+    pub fn read(&mut self, address: u16) -> u8 {
         match address {
             0x0000..=0x3FFF => {
                 // ROM Bank 0
@@ -136,16 +951,46 @@ impl Cartridge {
             }
             0x4000..=0x7FFF => {
                 // ROM Bank N
-                let bank_offset = self.current_rom_bank * 0x4000;
+                let bank_offset = self.masked_rom_bank() * 0x4000;
                 let local_address = (address - 0x4000) as usize;
                 self.rom_data[bank_offset + local_address]
             }
             0xA000..=0xBFFF => {
+                if self.mbc_type == MbcType::Mbc3 {
+                    if let Some(register) = self.rtc_register_select {
+                        return self.rtc.read_register(register);
+                    }
+                }
+                if self.mbc_type == MbcType::Mbc7 {
+                    return self.mbc7_read(address);
+                }
+                if self.mbc_type == MbcType::HuC1 {
+                    return if self.ram_enabled && !self.ram_data.is_empty() {
+                        self.ram_data[self.ram_address(address)]
+                    } else {
+                        0xC0 // IR port: always reports no signal received
+                    };
+                }
+                if self.mbc_type == MbcType::HuC3 {
+                    return match self.huc3_mode {
+                        0x0B => self.huc3_rtc.read_response(),
+                        0x0D => 0x00, // IR port: always reports no signal
+                        _ => {
+                            if self.ram_enabled && !self.ram_data.is_empty() {
+                                self.ram_data[self.ram_address(address)]
+                            } else {
+                                0xFF
+                            }
+                        }
+                    };
+                }
+                if self.mbc_type == MbcType::PocketCamera && self.current_ram_bank == 0x10 {
+                    return self.camera_read(address);
+                }
+
                 // External RAM
                 if self.ram_enabled && !self.ram_data.is_empty() {
-                    let bank_offset = self.current_ram_bank * 0x2000;
-                    let local_address = (address - 0xA000) as usize;
-                    self.ram_data[bank_offset + local_address]
+                    self.ram_data[self.ram_address(address)]
                 } else {
                     0xFF
                 }
@@ -154,6 +999,40 @@ impl Cartridge {
         }
     }
 
+    /// Read MBC7's tilt sensor / EEPROM register window (`address` anywhere
+    /// in `0xA000..=0xBFFF`; each register fills a `0x10`-byte slot and the
+    /// block repeats every `0x100` bytes).
+    fn mbc7_read(&self, address: u16) -> u8 {
+        let Some(mbc7) = &self.mbc7 else { return 0xFF };
+        match address & 0xF0 {
+            0x20 => (mbc7.accelerometer.latched_x & 0xFF) as u8,
+            0x30 => (mbc7.accelerometer.latched_x >> 8) as u8,
+            0x40 => (mbc7.accelerometer.latched_y & 0xFF) as u8,
+            0x50 => (mbc7.accelerometer.latched_y >> 8) as u8,
+            0x80 => mbc7.eeprom.read_do() as u8,
+            _ => 0x00,
+        }
+    }
+
+    /// Read the Pocket Camera's register block (`0xA000..=0xA035`) or its
+    /// developed-tile mirror (`0xA100..=0xAEFF`), active while RAM bank
+    /// `0x10` is selected.
+    fn camera_read(&mut self, address: u16) -> u8 {
+        let offset = (address - 0xA000) as usize;
+        let Some(camera) = &mut self.camera else {
+            return 0xFF;
+        };
+        if offset < CAM_REGISTER_COUNT {
+            camera.read_register(offset)
+        } else if (CAM_TILE_DATA_OFFSET..CAM_TILE_DATA_OFFSET + CAM_TILE_DATA_LEN)
+            .contains(&offset)
+        {
+            camera.read_tile_data(offset - CAM_TILE_DATA_OFFSET)
+        } else {
+            0x00
+        }
+    }
+
     /// Write to cartridge memory space
     pub fn write(&mut self, address: u16, value: u8) {
         match self.mbc_type {
@@ -172,6 +1051,18 @@ impl Cartridge {
             MbcType::Mbc5 => {
                 self.handle_mbc5_write(address, value);
             }
+            MbcType::Mbc7 => {
+                self.handle_mbc7_write(address, value);
+            }
+            MbcType::HuC1 => {
+                self.handle_huc1_write(address, value);
+            }
+            MbcType::HuC3 => {
+                self.handle_huc3_write(address, value);
+            }
+            MbcType::PocketCamera => {
+                self.handle_camera_write(address, value);
+            }
             MbcType::Unsupported(_) => {
                 // Unsupported MBC, ignore writes
             }
@@ -208,9 +1099,8 @@ impl Cartridge {
             0xA000..=0xBFFF => {
                 // External RAM Write
                 if self.ram_enabled && !self.ram_data.is_empty() {
-                    let bank_offset = self.current_ram_bank * 0x2000;
-                    let local_address = (address - 0xA000) as usize;
-                    self.ram_data[bank_offset + local_address] = value;
+                    let index = self.ram_address(address);
+                    self.ram_data[index] = value;
                 }
             }
             _ => {}
@@ -257,21 +1147,29 @@ impl Cartridge {
             }
             0x4000..=0x5FFF => {
                 // RAM Bank Number or RTC Register Select
-                if value <= 0x03 {
-                    self.current_ram_bank = value as usize;
+                match value {
+                    0x00..=0x03 => {
+                        self.current_ram_bank = value as usize;
+                        self.rtc_register_select = None;
+                    }
+                    0x08..=0x0C => {
+                        self.rtc_register_select = Some(value);
+                    }
+                    _ => {}
                 }
-                // RTC registers (0x08-0x0C) would be handled here
             }
             0x6000..=0x7FFF => {
-                // Latch Clock Data (RTC)
-                // RTC latching would be handled here
+                // Latch Clock Data: a 0x00 then 0x01 write copies the live
+                // RTC into the latched registers that 0xA000-0xBFFF reads.
+                self.rtc.handle_latch_write(value);
             }
             0xA000..=0xBFFF => {
-                // External RAM Write
-                if self.ram_enabled && !self.ram_data.is_empty() {
-                    let bank_offset = self.current_ram_bank * 0x2000;
-                    let local_address = (address - 0xA000) as usize;
-                    self.ram_data[bank_offset + local_address] = value;
+                if let Some(register) = self.rtc_register_select {
+                    self.rtc.write_register(register, value);
+                } else if self.ram_enabled && !self.ram_data.is_empty() {
+                    // External RAM Write
+                    let index = self.ram_address(address);
+                    self.ram_data[index] = value;
                 }
             }
             _ => {}
@@ -300,9 +1198,134 @@ impl Cartridge {
             0xA000..=0xBFFF => {
                 // External RAM Write
                 if self.ram_enabled && !self.ram_data.is_empty() {
-                    let bank_offset = self.current_ram_bank * 0x2000;
-                    let local_address = (address - 0xA000) as usize;
-                    self.ram_data[bank_offset + local_address] = value;
+                    let index = self.ram_address(address);
+                    self.ram_data[index] = value;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle MBC7 writes: ROM banking like MBC5, plus the accelerometer
+    /// latch and bit-banged EEPROM control register in place of RAM.
+    fn handle_mbc7_write(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => {
+                // RAM Enable (stored and saved; the register window below
+                // is accessible regardless of it)
+                self.ram_enabled = (value & 0x0F) == 0x0A;
+            }
+            0x2000..=0x3FFF => {
+                // ROM Bank Number
+                let bank = (value & 0x7F) as usize;
+                self.current_rom_bank = if bank == 0 { 1 } else { bank };
+            }
+            0xA000..=0xBFFF => {
+                if let Some(mbc7) = &mut self.mbc7 {
+                    match address & 0xF0 {
+                        0x00 => mbc7.accelerometer.handle_latch_write(value),
+                        0x80 => mbc7.eeprom.write_control(value),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle HuC1 writes: ROM/RAM banking like a simplified MBC1, except
+    /// `0x0000..=0x1FFF` toggles between RAM and the infrared port instead
+    /// of a plain RAM-enable latch.
+    fn handle_huc1_write(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => {
+                // 0x0E selects RAM; anything else selects the IR port.
+                self.ram_enabled = value == 0x0E;
+            }
+            0x2000..=0x3FFF => {
+                // ROM Bank Number
+                let bank = (value & 0x3F) as usize;
+                self.current_rom_bank = if bank == 0 { 1 } else { bank };
+            }
+            0x4000..=0x5FFF => {
+                // RAM Bank Number
+                self.current_ram_bank = (value & 0x03) as usize;
+            }
+            0xA000..=0xBFFF => {
+                // Writes to the IR port (ram_enabled == false) are accepted
+                // but have no effect, since the link always reports no
+                // signal.
+                if self.ram_enabled && !self.ram_data.is_empty() {
+                    let index = self.ram_address(address);
+                    self.ram_data[index] = value;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle HuC3 writes: ROM/RAM banking like a simplified MBC1, a
+    /// register-select at `0x0000..=0x1FFF` that picks what
+    /// `0xA000..=0xBFFF` means, and the RTC command protocol while that
+    /// select is `0x0B`.
+    fn handle_huc3_write(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => {
+                self.huc3_mode = value;
+                self.ram_enabled = value == 0x0A;
+            }
+            0x2000..=0x3FFF => {
+                // ROM Bank Number
+                let bank = (value & 0x3F) as usize;
+                self.current_rom_bank = if bank == 0 { 1 } else { bank };
+            }
+            0x4000..=0x5FFF => {
+                // RAM Bank Number
+                self.current_ram_bank = (value & 0x03) as usize;
+            }
+            0xA000..=0xBFFF => match self.huc3_mode {
+                0x0B => self.huc3_rtc.write_command(value),
+                0x0D => {} // IR port: writes accepted, no effect
+                _ => {
+                    if self.ram_enabled && !self.ram_data.is_empty() {
+                        let index = self.ram_address(address);
+                        self.ram_data[index] = value;
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Handle Pocket Camera writes: MBC3-like ROM banking, a RAM bank
+    /// register that also accepts the special value `0x10` selecting the
+    /// sensor register window instead of a RAM bank, and register writes
+    /// (including the capture trigger) while that window is selected.
+    fn handle_camera_write(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => {
+                self.ram_enabled = (value & 0x0F) == 0x0A;
+            }
+            0x2000..=0x3FFF => {
+                // ROM Bank Number
+                let bank = (value & 0x7F) as usize;
+                self.current_rom_bank = if bank == 0 { 1 } else { bank };
+            }
+            0x4000..=0x5FFF => {
+                // RAM Bank Number, or 0x10 to select the sensor registers.
+                self.current_ram_bank = (value & 0x1F) as usize;
+            }
+            0xA000..=0xBFFF => {
+                if self.current_ram_bank == 0x10 {
+                    let offset = (address - 0xA000) as usize;
+                    if let Some(camera) = &mut self.camera {
+                        if offset < CAM_REGISTER_COUNT {
+                            camera.write_register(offset, value);
+                        }
+                    }
+                } else if self.ram_enabled && !self.ram_data.is_empty() {
+                    let index = self.ram_address(address);
+                    self.ram_data[index] = value;
                 }
             }
             _ => {}
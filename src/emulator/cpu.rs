@@ -16,6 +16,7 @@ bitflags! {
 }
 
 /// LR35902 CPU state
+#[derive(Clone, PartialEq, Eq)]
 pub struct Cpu {
     // Registers
     pub a: u8,
@@ -40,21 +41,61 @@ pub struct Cpu {
 impl Cpu {
     /// Create a new CPU instance
     pub fn new() -> Self {
-        Self {
-            a: 0x01,
-            b: 0x00,
-            c: 0x13,
-            d: 0x00,
-            e: 0xD8,
-            h: 0x01,
-            l: 0x4D,
-            flags: Flags::from_bits_truncate(0xB0),
-            pc: 0x0100,
-            sp: 0xFFFE,
+        let mut cpu = Self {
+            a: 0,
+            b: 0,
+            c: 0,
+            d: 0,
+            e: 0,
+            h: 0,
+            l: 0,
+            flags: Flags::empty(),
+            pc: 0,
+            sp: 0,
             cycles: 0,
             halted: false,
             ime: false,
-        }
+        };
+        cpu.reset_after_boot();
+        cpu
+    }
+
+    /// Set registers to the documented post-boot state (the values the DMG
+    /// boot ROM leaves behind just before jumping to `0x0100`), so runs that
+    /// skip the boot ROM converge to the same state as ones that play it.
+    pub fn reset_after_boot(&mut self) {
+        self.a = 0x01;
+        self.b = 0x00;
+        self.c = 0x13;
+        self.d = 0x00;
+        self.e = 0xD8;
+        self.h = 0x01;
+        self.l = 0x4D;
+        self.flags = Flags::from_bits_truncate(0xB0);
+        self.pc = 0x0100;
+        self.sp = 0xFFFE;
+        self.cycles = 0;
+        self.halted = false;
+        self.ime = false;
+    }
+
+    /// Set registers to the true power-on state (all zero), for runs that
+    /// load the real boot ROM and let it execute from `0x0000` instead of
+    /// jumping straight to the post-boot state `reset_after_boot` leaves.
+    pub fn reset_for_boot_rom(&mut self) {
+        self.a = 0x00;
+        self.b = 0x00;
+        self.c = 0x00;
+        self.d = 0x00;
+        self.e = 0x00;
+        self.h = 0x00;
+        self.l = 0x00;
+        self.flags = Flags::empty();
+        self.pc = 0x0000;
+        self.sp = 0x0000;
+        self.cycles = 0;
+        self.halted = false;
+        self.ime = false;
     }
 
     /// Execute one CPU instruction
@@ -68,6 +109,42 @@ impl Cpu {
         // This will be implemented with the full instruction set
     }
 
+    /// Serialize all registers and internal state for a save state.
+    pub fn save_state(&self, buf: &mut Vec<u8>) {
+        buf.push(self.a);
+        buf.push(self.b);
+        buf.push(self.c);
+        buf.push(self.d);
+        buf.push(self.e);
+        buf.push(self.h);
+        buf.push(self.l);
+        buf.push(self.flags.bits());
+        buf.extend_from_slice(&self.pc.to_le_bytes());
+        buf.extend_from_slice(&self.sp.to_le_bytes());
+        buf.extend_from_slice(&self.cycles.to_le_bytes());
+        buf.push(self.halted as u8);
+        buf.push(self.ime as u8);
+    }
+
+    /// Restore state previously produced by `save_state` from the front of
+    /// `data`, returning the number of bytes consumed.
+    pub fn load_state(&mut self, data: &[u8]) -> usize {
+        self.a = data[0];
+        self.b = data[1];
+        self.c = data[2];
+        self.d = data[3];
+        self.e = data[4];
+        self.h = data[5];
+        self.l = data[6];
+        self.flags = Flags::from_bits_truncate(data[7]);
+        self.pc = u16::from_le_bytes([data[8], data[9]]);
+        self.sp = u16::from_le_bytes([data[10], data[11]]);
+        self.cycles = u64::from_le_bytes(data[12..20].try_into().unwrap());
+        self.halted = data[20] != 0;
+        self.ime = data[21] != 0;
+        22
+    }
+
     /// Get combined AF register
     pub fn af(&self) -> u16 {
         (self.a as u16) << 8 | self.flags.bits() as u16
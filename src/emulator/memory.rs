@@ -2,6 +2,8 @@
 //!
 //! Handles memory mapping and access for the Game Boy's address space.
 
+use crate::emulator::cartridge::Cartridge;
+use crate::emulator::ppu::Ppu;
 use crate::EmulatorError;
 
 /// Game Boy memory map constants
@@ -27,65 +29,189 @@ pub const HRAM_START: u16 = 0xFF80;
 pub const HRAM_END: u16 = 0xFFFE;
 pub const INTERRUPT_ENABLE_REGISTER: u16 = 0xFFFF;
 
+/// Joypad register. Bits 4-5 select which button group the lower nibble
+/// reads back (direction keys / action buttons); both can be selected at
+/// once, in which case the real hardware wire-ANDs the two nibbles
+/// together. Bits 0-3 are active-low (0 = pressed) and bits 6-7 always
+/// read back as 1.
+const JOYP_REGISTER: u16 = 0xFF00;
+
+/// IO register that unmaps the boot ROM when written.
+const BOOT_ROM_DISABLE_REGISTER: u16 = 0xFF50;
+
+/// IO register that starts an OAM DMA transfer when written.
+const OAM_DMA_REGISTER: u16 = 0xFF46;
+
+/// Number of bytes an OAM DMA transfer copies.
+const OAM_DMA_LENGTH: u8 = 0xA0;
+
+/// PPU control/scroll/palette registers, mirrored into `Ppu`'s fields on
+/// write so the renderer sees live values while `io_registers` stays the
+/// authoritative, serialized copy of the address space.
+const LCDC_REGISTER: u16 = 0xFF40;
+const STAT_REGISTER: u16 = 0xFF41;
+const SCY_REGISTER: u16 = 0xFF42;
+const SCX_REGISTER: u16 = 0xFF43;
+/// Current scanline. Read-only on real hardware: reads are served live from
+/// `Ppu::scanline` rather than `io_registers`, and writes are ignored.
+const LY_REGISTER: u16 = 0xFF44;
+const BGP_REGISTER: u16 = 0xFF47;
+const OBP0_REGISTER: u16 = 0xFF48;
+const OBP1_REGISTER: u16 = 0xFF49;
+const WY_REGISTER: u16 = 0xFF4A;
+const WX_REGISTER: u16 = 0xFF4B;
+
+/// In-flight OAM DMA transfer state: writing `source_high` to `0xFF46`
+/// starts a copy of `source_high00..=source_high9F` into OAM, one byte per
+/// machine cycle, via `Mmu::step_dma`.
+#[derive(Default)]
+struct DmaState {
+    source_high: u8,
+    /// Bytes still to copy; a transfer is active while this is non-zero.
+    remaining: u8,
+}
+
 /// Memory management unit
 pub struct Mmu {
-    // ROM banks (cartridge)
-    rom_bank_0: [u8; 0x4000],
-    rom_bank_n: [u8; 0x4000],
-    
-    // Video RAM
-    vram: [u8; 0x2000],
-    
-    // External RAM (cartridge)
-    external_ram: [u8; 0x2000],
-    
+    // Cartridge ROM/RAM bank switching, selected at `load_rom` time
+    cartridge: Cartridge,
+
+    // Picture Processing Unit, owning VRAM and OAM
+    ppu: Ppu,
+
     // Work RAM
     wram: [u8; 0x2000],
-    
-    // Object Attribute Memory (sprites)
-    oam: [u8; 0xA0],
-    
+
     // I/O Registers
     io_registers: [u8; 0x80],
-    
+
     // High RAM
     hram: [u8; 0x7F],
-    
+
     // Interrupt Enable Register
     interrupt_enable: u8,
+
+    // The 256-byte DMG boot ROM, if one was supplied
+    boot_rom: Option<[u8; 0x100]>,
+
+    // Whether `0x0000..=0x00FF` reads should be served from `boot_rom`
+    // rather than cartridge ROM; cleared permanently by a write to
+    // `0xFF50`.
+    boot_rom_active: bool,
+
+    // In-flight OAM DMA transfer, advanced by `step_dma`
+    dma: DmaState,
+
+    // Button-group select bits (0xFF00 bits 4-5) last written by the game
+    joyp_select: u8,
+
+    // Current button state, active-low, one bit per button: bit0=right,
+    // bit1=left, bit2=up, bit3=down, bit4=A, bit5=B, bit6=select,
+    // bit7=start. Set from the host frontend via `set_button_state`.
+    button_state: u8,
 }
 
 impl Mmu {
     /// Create a new MMU instance
     pub fn new() -> Self {
         Self {
-            rom_bank_0: [0; 0x4000],
-            rom_bank_n: [0; 0x4000],
-            vram: [0; 0x2000],
-            external_ram: [0; 0x2000],
+            cartridge: Cartridge::new(vec![0; 0x8000], None)
+                .expect("a zeroed 32KB ROM is always a valid (empty) cartridge"),
+            ppu: Ppu::new(),
             wram: [0; 0x2000],
-            oam: [0; 0xA0],
             io_registers: [0; 0x80],
             hram: [0; 0x7F],
             interrupt_enable: 0,
+            boot_rom: None,
+            boot_rom_active: false,
+            dma: DmaState::default(),
+            joyp_select: 0x30,
+            button_state: 0xFF,
         }
     }
 
-    /// Read a byte from memory
-    pub fn read_byte(&self, address: u16) -> u8 {
-        match address {
-            ROM_BANK_0_START..=ROM_BANK_0_END => {
-                self.rom_bank_0[(address - ROM_BANK_0_START) as usize]
-            }
-            ROM_BANK_N_START..=ROM_BANK_N_END => {
-                self.rom_bank_n[(address - ROM_BANK_N_START) as usize]
-            }
-            VRAM_START..=VRAM_END => {
-                self.vram[(address - VRAM_START) as usize]
-            }
-            EXTERNAL_RAM_START..=EXTERNAL_RAM_END => {
-                self.external_ram[(address - EXTERNAL_RAM_START) as usize]
+    /// Load the 256-byte DMG boot ROM and enable the overlay so
+    /// `0x0000..=0x00FF` reads come from it until `0xFF50` is written.
+    pub fn load_boot_rom(&mut self, boot_rom: [u8; 0x100]) {
+        self.boot_rom = Some(boot_rom);
+        self.boot_rom_active = true;
+    }
+
+    /// Set LCDC, BGP, OBP0, OBP1 and the interrupt-enable register to their
+    /// documented post-boot values. Used by `reset_after_boot` when no boot
+    /// ROM was loaded. The other IO registers (timer, sound, scroll, ...)
+    /// are not set and keep whatever value they already held.
+    fn apply_post_boot_io_defaults(&mut self) {
+        self.io_registers[(0xFF40 - IO_REGISTERS_START) as usize] = 0x91; // LCDC
+        self.io_registers[(0xFF47 - IO_REGISTERS_START) as usize] = 0xFC; // BGP
+        self.io_registers[(0xFF48 - IO_REGISTERS_START) as usize] = 0xFF; // OBP0
+        self.io_registers[(0xFF49 - IO_REGISTERS_START) as usize] = 0xFF; // OBP1
+        self.interrupt_enable = 0x00;
+        self.ppu.lcdc = 0x91;
+        self.ppu.bg_palette = 0xFC;
+        self.ppu.obj_palette_0 = 0xFF;
+        self.ppu.obj_palette_1 = 0xFF;
+    }
+
+    /// Skip the boot ROM: disable the overlay (if any) and apply the
+    /// post-boot IO values set by `apply_post_boot_io_defaults`.
+    pub fn reset_after_boot(&mut self) {
+        self.boot_rom_active = false;
+        self.apply_post_boot_io_defaults();
+    }
+
+    /// Borrow the PPU mutably, so `GameBoy::step` can advance it and
+    /// `GameBoy::get_frame_buffer` can read its output.
+    pub fn ppu_mut(&mut self) -> &mut Ppu {
+        &mut self.ppu
+    }
+
+    /// Borrow the PPU immutably.
+    pub fn ppu(&self) -> &Ppu {
+        &self.ppu
+    }
+
+    /// Set the current button state, active-low, one bit per button
+    /// (bit0=right, bit1=left, bit2=up, bit3=down, bit4=A, bit5=B,
+    /// bit6=select, bit7=start), for `0xFF00` reads to report.
+    pub fn set_button_state(&mut self, state: u8) {
+        self.button_state = state;
+    }
+
+    /// Resolve a `0xFF00` read: bits 6-7 always read 1, and the lower
+    /// nibble is the direction or action-button group selected by bits
+    /// 4-5 of the last write (wire-ANDed together if both are selected).
+    fn read_joyp(&self) -> u8 {
+        let mut low_nibble = 0x0F;
+        if self.joyp_select & 0x10 == 0 {
+            low_nibble &= self.button_state & 0x0F;
+        }
+        if self.joyp_select & 0x20 == 0 {
+            low_nibble &= (self.button_state >> 4) & 0x0F;
+        }
+        0xC0 | self.joyp_select | low_nibble
+    }
+
+    /// Borrow the cartridge's battery-backed save RAM mutably, so platform
+    /// frontends (e.g. the libretro core's `retro_get_memory_data`) can hand
+    /// it to a host that persists `.srm` files itself.
+    pub fn save_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.cartridge.ram_data
+    }
+
+    /// Read a byte from memory. `&mut self` because cartridge reads can
+    /// drain stateful protocol queues (HuC3's RTC nibble response).
+    pub fn read_byte(&mut self, address: u16) -> u8 {
+        if self.boot_rom_active && address <= 0x00FF {
+            if let Some(boot_rom) = &self.boot_rom {
+                return boot_rom[address as usize];
             }
+        }
+
+        match address {
+            ROM_BANK_0_START..=ROM_BANK_N_END => self.cartridge.read(address),
+            VRAM_START..=VRAM_END => self.ppu.read_vram(address - VRAM_START),
+            EXTERNAL_RAM_START..=EXTERNAL_RAM_END => self.cartridge.read(address),
             WRAM_START..=WRAM_END => {
                 self.wram[(address - WRAM_START) as usize]
             }
@@ -93,13 +219,13 @@ impl Mmu {
                 // Echo of WRAM
                 self.wram[(address - ECHO_RAM_START) as usize]
             }
-            OAM_START..=OAM_END => {
-                self.oam[(address - OAM_START) as usize]
-            }
+            OAM_START..=OAM_END => self.ppu.read_oam(address - OAM_START),
             UNUSED_START..=UNUSED_END => {
                 // Unused memory space
                 0xFF
             }
+            LY_REGISTER => self.ppu.scanline,
+            JOYP_REGISTER => self.read_joyp(),
             IO_REGISTERS_START..=IO_REGISTERS_END => {
                 self.io_registers[(address - IO_REGISTERS_START) as usize]
             }
@@ -115,20 +241,9 @@ impl Mmu {
     /// Write a byte to memory
     pub fn write_byte(&mut self, address: u16, value: u8) {
         match address {
-            ROM_BANK_0_START..=ROM_BANK_0_END => {
-                // ROM is read-only, but MBC may handle this
-                // For now, ignore writes to ROM
-            }
-            ROM_BANK_N_START..=ROM_BANK_N_END => {
-                // ROM is read-only, but MBC may handle this
-                // For now, ignore writes to ROM
-            }
-            VRAM_START..=VRAM_END => {
-                self.vram[(address - VRAM_START) as usize] = value;
-            }
-            EXTERNAL_RAM_START..=EXTERNAL_RAM_END => {
-                self.external_ram[(address - EXTERNAL_RAM_START) as usize] = value;
-            }
+            ROM_BANK_0_START..=ROM_BANK_N_END => self.cartridge.write(address, value),
+            VRAM_START..=VRAM_END => self.ppu.write_vram(address - VRAM_START, value),
+            EXTERNAL_RAM_START..=EXTERNAL_RAM_END => self.cartridge.write(address, value),
             WRAM_START..=WRAM_END => {
                 self.wram[(address - WRAM_START) as usize] = value;
             }
@@ -136,14 +251,40 @@ impl Mmu {
                 // Echo of WRAM
                 self.wram[(address - ECHO_RAM_START) as usize] = value;
             }
-            OAM_START..=OAM_END => {
-                self.oam[(address - OAM_START) as usize] = value;
-            }
+            OAM_START..=OAM_END => self.ppu.write_oam(address - OAM_START, value),
             UNUSED_START..=UNUSED_END => {
                 // Unused memory space, ignore writes
             }
+            JOYP_REGISTER => {
+                self.joyp_select = value & 0x30;
+                self.io_registers[(address - IO_REGISTERS_START) as usize] = value;
+            }
+            BOOT_ROM_DISABLE_REGISTER => {
+                self.boot_rom_active = false;
+                self.io_registers[(address - IO_REGISTERS_START) as usize] = value;
+            }
+            OAM_DMA_REGISTER => {
+                self.dma.source_high = value;
+                self.dma.remaining = OAM_DMA_LENGTH;
+                self.io_registers[(address - IO_REGISTERS_START) as usize] = value;
+            }
+            LY_REGISTER => {
+                // Read-only on real hardware; writes have no effect.
+            }
             IO_REGISTERS_START..=IO_REGISTERS_END => {
                 self.io_registers[(address - IO_REGISTERS_START) as usize] = value;
+                match address {
+                    LCDC_REGISTER => self.ppu.lcdc = value,
+                    STAT_REGISTER => self.ppu.stat = value,
+                    SCY_REGISTER => self.ppu.scroll_y = value,
+                    SCX_REGISTER => self.ppu.scroll_x = value,
+                    BGP_REGISTER => self.ppu.bg_palette = value,
+                    OBP0_REGISTER => self.ppu.obj_palette_0 = value,
+                    OBP1_REGISTER => self.ppu.obj_palette_1 = value,
+                    WY_REGISTER => self.ppu.window_y = value,
+                    WX_REGISTER => self.ppu.window_x = value,
+                    _ => {}
+                }
             }
             HRAM_START..=HRAM_END => {
                 self.hram[(address - HRAM_START) as usize] = value;
@@ -155,7 +296,7 @@ impl Mmu {
     }
 
     /// Read a 16-bit word from memory (little-endian)
-    pub fn read_word(&self, address: u16) -> u16 {
+    pub fn read_word(&mut self, address: u16) -> u16 {
         let low = self.read_byte(address) as u16;
         let high = self.read_byte(address + 1) as u16;
         (high << 8) | low
@@ -167,26 +308,104 @@ impl Mmu {
         self.write_byte(address + 1, (value >> 8) as u8);
     }
 
-    /// Load ROM data into memory
+    /// Load ROM data, building a `Cartridge` from the header's MBC type byte.
     pub fn load_rom(&mut self, rom_data: &[u8]) -> Result<(), EmulatorError> {
-        if rom_data.len() < 0x4000 {
-            return Err(EmulatorError::InvalidRom);
-        }
+        self.cartridge = Cartridge::new(rom_data.to_vec(), None)?;
+        Ok(())
+    }
 
-        // Load ROM bank 0
-        self.rom_bank_0.copy_from_slice(&rom_data[0..0x4000]);
-        
-        // Load ROM bank 1 if available
-        if rom_data.len() >= 0x8000 {
-            self.rom_bank_n.copy_from_slice(&rom_data[0x4000..0x8000]);
+    /// Advance an in-flight OAM DMA transfer by `cycles` machine cycles,
+    /// copying one byte from `source_high:00..=source_high:9F` into OAM per
+    /// cycle. A no-op when no transfer is active.
+    pub fn step_dma(&mut self, cycles: u32) {
+        for _ in 0..cycles {
+            if self.dma.remaining == 0 {
+                break;
+            }
+            let offset = OAM_DMA_LENGTH - self.dma.remaining;
+            let source = ((self.dma.source_high as u16) << 8) | offset as u16;
+            let byte = self.read_byte(source);
+            self.ppu.write_oam(offset as u16, byte);
+            self.dma.remaining -= 1;
         }
+    }
 
-        Ok(())
+    /// Serialize every memory region, the in-flight DMA state, the PPU, and
+    /// the cartridge's bank registers/RAM for a save state.
+    pub fn save_state(&self, buf: &mut Vec<u8>) {
+        self.ppu.save_state(buf);
+        buf.extend_from_slice(&self.wram);
+        buf.extend_from_slice(&self.io_registers);
+        buf.extend_from_slice(&self.hram);
+        buf.push(self.interrupt_enable);
+        buf.push(self.boot_rom_active as u8);
+        buf.push(self.dma.source_high);
+        buf.push(self.dma.remaining);
+        buf.push(self.joyp_select);
+        buf.push(self.button_state);
+
+        let cartridge_state = self.cartridge.save_state();
+        buf.extend_from_slice(&(cartridge_state.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&cartridge_state);
+    }
+
+    /// Restore state previously produced by `save_state` from the front of
+    /// `data`, returning the number of bytes consumed.
+    pub fn load_state(&mut self, data: &[u8]) -> usize {
+        let mut offset = self.ppu.load_state(data);
+
+        self.wram.copy_from_slice(&data[offset..offset + 0x2000]);
+        offset += 0x2000;
+        self.io_registers
+            .copy_from_slice(&data[offset..offset + 0x80]);
+        offset += 0x80;
+        self.hram.copy_from_slice(&data[offset..offset + 0x7F]);
+        offset += 0x7F;
+
+        self.interrupt_enable = data[offset];
+        offset += 1;
+        self.boot_rom_active = data[offset] != 0;
+        offset += 1;
+        self.dma.source_high = data[offset];
+        offset += 1;
+        self.dma.remaining = data[offset];
+        offset += 1;
+        self.joyp_select = data[offset];
+        offset += 1;
+        self.button_state = data[offset];
+        offset += 1;
+
+        let cartridge_len =
+            u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        offset += 4;
+        self.cartridge
+            .load_state(&data[offset..offset + cartridge_len]);
+        offset += cartridge_len;
+
+        offset
     }
 }
 
+impl PartialEq for Mmu {
+    fn eq(&self, other: &Self) -> bool {
+        self.ppu == other.ppu
+            && self.wram == other.wram
+            && self.io_registers == other.io_registers
+            && self.hram == other.hram
+            && self.interrupt_enable == other.interrupt_enable
+            && self.boot_rom_active == other.boot_rom_active
+            && self.dma.source_high == other.dma.source_high
+            && self.dma.remaining == other.dma.remaining
+            && self.joyp_select == other.joyp_select
+            && self.button_state == other.button_state
+            && self.cartridge.save_state() == other.cartridge.save_state()
+    }
+}
+
+impl Eq for Mmu {}
+
 impl Default for Mmu {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
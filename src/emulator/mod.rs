@@ -2,24 +2,61 @@
 //!
 //! This module contains the main emulation logic including CPU, PPU, APU, and memory management.
 
+pub mod audio;
 pub mod cpu;
 pub mod memory;
 pub mod ppu;
 pub mod apu;
 pub mod cartridge;
+pub mod sched;
 
+use crate::emulator::apu::{Apu, FRAME_SEQUENCER_PERIOD};
+use crate::emulator::audio::{AudioConsumer, DEFAULT_CAPACITY};
+use crate::emulator::cpu::Cpu;
+use crate::emulator::memory::Mmu;
+use crate::emulator::sched::{EventType, Scheduler};
 use crate::EmulatorError;
 
+/// Bumped whenever the save-state layout changes, so a stale blob is
+/// rejected instead of silently corrupting the machine.
+const SAVE_STATE_VERSION: u8 = 1;
+
 /// Main Game Boy emulator struct
 pub struct GameBoy {
-    // Core emulator components will be added here
+    cpu: Cpu,
+    mmu: Mmu,
+    apu: Apu,
+    audio_consumer: AudioConsumer,
+    /// Scratch space `get_audio_samples` fills from `audio_consumer` so it
+    /// can keep handing back a borrowed slice for existing callers.
+    audio_scratch: Vec<i16>,
+    /// Drives hardware timing events (currently just the APU's 512 Hz frame
+    /// sequencer) so the core loop can run the CPU/DMA/APU/PPU in bursts
+    /// between events instead of dispatching one cycle at a time.
+    scheduler: Scheduler,
+    // Other core emulator components will be added here
 }
 
 impl GameBoy {
     /// Create a new Game Boy emulator instance
     pub fn new() -> Self {
+        let (producer, audio_consumer) = audio::ring_buffer(DEFAULT_CAPACITY);
+        let mut apu = Apu::new();
+        apu.set_audio_interface(Box::new(producer));
+
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(EventType::ApuFrameSequencer, FRAME_SEQUENCER_PERIOD);
+
+        let mut mmu = Mmu::new();
+        mmu.reset_after_boot();
+
         Self {
-            // Initialize components
+            cpu: Cpu::new(),
+            mmu,
+            apu,
+            audio_consumer,
+            audio_scratch: Vec::new(),
+            scheduler,
         }
     }
 
@@ -28,26 +65,130 @@ impl GameBoy {
         if rom_data.is_empty() {
             return Err(EmulatorError::InvalidRom);
         }
-        
-        // ROM loading logic will be implemented here
-        Ok(())
+
+        self.mmu.load_rom(rom_data)
+    }
+
+    /// Load the 256-byte DMG boot ROM and reset the CPU to the true
+    /// power-on state so it executes the boot ROM from `0x0000` instead of
+    /// the post-boot state `new` otherwise leaves the machine in.
+    pub fn load_boot_rom(&mut self, boot_rom: [u8; 0x100]) {
+        self.mmu.load_boot_rom(boot_rom);
+        self.cpu.reset_for_boot_rom();
+    }
+
+    /// Run one burst: advance the scheduler to the next due event, step the
+    /// CPU/DMA/APU/PPU that many cycles, then dispatch whatever came due
+    /// (re-arming recurring events like the frame sequencer). Returns the
+    /// number of cycles the burst advanced, since callers that need to stop
+    /// at a fixed cycle budget (see `run_cycles`) can no longer assume one
+    /// cycle per call.
+    pub fn step(&mut self) -> u64 {
+        let burst_cycles = self.scheduler.run_until_next_event().max(1);
+        for _ in 0..burst_cycles {
+            self.cpu.step();
+            self.mmu.step_dma(1);
+            self.apu.step();
+            self.mmu.ppu_mut().step();
+        }
+
+        while let Some(event) = self.scheduler.pop_ready() {
+            match event {
+                EventType::ApuFrameSequencer => {
+                    self.apu.clock_frame_sequencer();
+                    self.scheduler
+                        .schedule(EventType::ApuFrameSequencer, FRAME_SEQUENCER_PERIOD);
+                }
+                // Per-channel timer and PPU mode-change events are added to
+                // the scheduler as those subsystems move off their own
+                // cycle accumulators.
+                _ => {}
+            }
+        }
+
+        burst_cycles
+    }
+
+    /// Run bursts via `step` until at least `cycles` machine cycles have
+    /// elapsed, e.g. `run_cycles(70224)` for one ~59.7 Hz video frame.
+    /// `step` no longer advances exactly one cycle per call, so frontends
+    /// driving a fixed cycle budget per frame should use this instead of
+    /// looping `step` themselves.
+    pub fn run_cycles(&mut self, cycles: u64) {
+        let mut elapsed = 0;
+        while elapsed < cycles {
+            elapsed += self.step();
+        }
+    }
+
+    /// Serialize the machine state: CPU registers, all MMU memory regions
+    /// (including the PPU), and the mapper's bank registers/RAM. The APU and
+    /// scheduler state are not included.
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut buf = vec![SAVE_STATE_VERSION];
+        self.cpu.save_state(&mut buf);
+        self.mmu.save_state(&mut buf);
+        buf
     }
 
-    /// Execute one CPU cycle
-    pub fn step(&mut self) {
-        // CPU step logic will be implemented here
+    /// Restore state previously produced by `save_state`.
+    pub fn load_state(&mut self, data: &[u8]) -> Result<(), EmulatorError> {
+        if data.first() != Some(&SAVE_STATE_VERSION) {
+            return Err(EmulatorError::MemoryError(
+                "unsupported save state version".to_string(),
+            ));
+        }
+        let mut offset = 1;
+        offset += self.cpu.load_state(&data[offset..]);
+        self.mmu.load_state(&data[offset..]);
+        Ok(())
     }
 
     /// Get the current frame buffer for rendering
     pub fn get_frame_buffer(&self) -> &[u8] {
-        // Return frame buffer data
-        &[]
+        self.mmu.ppu().get_frame_buffer()
+    }
+
+    /// Borrow the cartridge's battery-backed save RAM mutably, for
+    /// frontends that expose it to their host instead of managing save
+    /// files themselves (see the libretro core's `retro_get_memory_data`).
+    pub fn save_ram_mut(&mut self) -> &mut [u8] {
+        self.mmu.save_ram_mut()
+    }
+
+    /// Set the current button state, active-low, one bit per button
+    /// (bit0=right, bit1=left, bit2=up, bit3=down, bit4=A, bit5=B,
+    /// bit6=select, bit7=start), for the `0xFF00` joypad register to
+    /// report on the next read.
+    pub fn set_button_state(&mut self, state: u8) {
+        self.mmu.set_button_state(state);
+    }
+
+    /// Drain the audio consumer and return the interleaved L/R samples
+    /// produced since the last call. Prefer `audio_consumer()` directly
+    /// for a platform callback that wants to pull frames on its own
+    /// schedule without this buffer indirection.
+    pub fn get_audio_samples(&mut self) -> &[i16] {
+        self.audio_scratch.clear();
+        while let Some(frame) = self.audio_consumer.pop() {
+            self.audio_scratch.push(frame.left);
+            self.audio_scratch.push(frame.right);
+        }
+        &self.audio_scratch
+    }
+
+    /// Borrow the consumer half of the APU's audio ring buffer, for
+    /// platform code (a `cpal` callback, a WASM `AudioWorklet`) that wants
+    /// to pull samples directly instead of through `get_audio_samples`.
+    pub fn audio_consumer(&mut self) -> &mut AudioConsumer {
+        &mut self.audio_consumer
     }
 
-    /// Get audio samples for the current frame
-    pub fn get_audio_samples(&self) -> &[i16] {
-        // Return audio samples
-        &[]
+    /// Borrow the CPU and MMU directly, for the `gdb` debug stub to drive
+    /// against a live machine instead of a standalone pair.
+    #[cfg(feature = "gdb")]
+    pub fn debug_parts(&mut self) -> (&mut Cpu, &mut Mmu) {
+        (&mut self.cpu, &mut self.mmu)
     }
 }
 
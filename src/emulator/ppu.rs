@@ -7,7 +7,7 @@ pub const LCD_WIDTH: usize = 160;
 pub const LCD_HEIGHT: usize = 144;
 
 /// PPU rendering states
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PpuMode {
     HBlank = 0,
     VBlank = 1,
@@ -15,38 +15,57 @@ pub enum PpuMode {
     Drawing = 3,
 }
 
+/// One OAM entry is 4 bytes: Y, X, tile index, attributes.
+const OAM_ENTRY_SIZE: usize = 4;
+
+/// Up to 40 sprites in OAM, at most 10 of which are drawn per scanline.
+const OAM_ENTRY_COUNT: usize = 40;
+const MAX_SPRITES_PER_LINE: usize = 10;
+
 /// Picture Processing Unit
+#[derive(PartialEq, Eq)]
 pub struct Ppu {
     /// Frame buffer (160x144 pixels, 4 shades of gray)
     pub frame_buffer: [u8; LCD_WIDTH * LCD_HEIGHT],
-    
+
     /// Current scanline being processed
     pub scanline: u8,
-    
+
     /// Current PPU mode
     pub mode: PpuMode,
-    
+
     /// Cycle counter for timing
     pub cycles: u32,
-    
+
     /// LCD control register
     pub lcdc: u8,
-    
+
     /// LCD status register
     pub stat: u8,
-    
+
     /// Scroll registers
     pub scroll_x: u8,
     pub scroll_y: u8,
-    
+
     /// Window position
     pub window_x: u8,
     pub window_y: u8,
-    
+
     /// Background and window palettes
     pub bg_palette: u8,
     pub obj_palette_0: u8,
     pub obj_palette_1: u8,
+
+    /// Video RAM: tile data (`0x8000..=0x97FF`) and the two tile maps
+    /// (`0x9800..=0x9BFF`, `0x9C00..=0x9FFF`), indexed relative to `0x8000`.
+    pub vram: [u8; 0x2000],
+
+    /// Object Attribute Memory: 40 sprites * 4 bytes (Y, X, tile, attributes).
+    pub oam: [u8; 0xA0],
+
+    /// Internal window line counter: increments only on scanlines where the
+    /// window is actually drawn, since it can start partway down the frame.
+    window_line: u8,
 }
 
 impl Ppu {
@@ -66,9 +85,62 @@ impl Ppu {
             bg_palette: 0xFC,
             obj_palette_0: 0xFF,
             obj_palette_1: 0xFF,
+            vram: [0; 0x2000],
+            oam: [0; 0xA0],
+            window_line: 0,
+        }
+    }
+
+    /// Write a byte into VRAM (`address` relative to `0x8000`).
+    pub fn write_vram(&mut self, address: u16, value: u8) {
+        self.vram[address as usize] = value;
+    }
+
+    /// Read a byte from VRAM (`address` relative to `0x8000`).
+    pub fn read_vram(&self, address: u16) -> u8 {
+        self.vram[address as usize]
+    }
+
+    /// Write a byte into OAM (`address` relative to `0xFE00`).
+    pub fn write_oam(&mut self, address: u16, value: u8) {
+        self.oam[address as usize] = value;
+    }
+
+    /// Read a byte from OAM (`address` relative to `0xFE00`).
+    pub fn read_oam(&self, address: u16) -> u8 {
+        self.oam[address as usize]
+    }
+
+    /// Map a tile index to its offset into `vram`, honoring LCDC bit 4:
+    /// unsigned indexing from `0x8000` when set, signed indexing from the
+    /// `0x9000` midpoint when clear.
+    fn tile_data_offset(&self, tile_index: u8) -> usize {
+        if self.lcdc & 0x10 != 0 {
+            tile_index as usize * 16
+        } else {
+            let signed_index = tile_index as i8 as i32;
+            (0x1000 + signed_index * 16) as usize
         }
     }
 
+    /// Read the two bitplane bytes for `tile_data_offset`'s tile, row
+    /// `row_in_tile` (0-7), and combine bit `7-col_in_tile` of each plane
+    /// into a 2-bit color index (0-3).
+    fn tile_pixel(&self, tile_data_offset: usize, row_in_tile: u8, col_in_tile: u8) -> u8 {
+        let row_offset = tile_data_offset + row_in_tile as usize * 2;
+        let low_byte = self.vram[row_offset];
+        let high_byte = self.vram[row_offset + 1];
+        let bit = 7 - col_in_tile;
+        let low = (low_byte >> bit) & 0x01;
+        let high = (high_byte >> bit) & 0x01;
+        (high << 1) | low
+    }
+
+    /// Apply a DMG palette register to a 2-bit color index.
+    fn apply_palette(palette: u8, color: u8) -> u8 {
+        (palette >> (color * 2)) & 0x03
+    }
+
     /// Step the PPU by one cycle
     pub fn step(&mut self) {
         self.cycles += 1;
@@ -107,6 +179,7 @@ impl Ppu {
                     
                     if self.scanline >= 154 {
                         self.scanline = 0;
+                        self.window_line = 0;
                         self.mode = PpuMode::OamScan;
                     }
                 }
@@ -144,19 +217,132 @@ impl Ppu {
 
     /// Render background tiles for the current scanline
     fn render_background(&mut self) {
-        // Background rendering logic will be implemented here
-        // This involves reading tile data from VRAM and applying palettes
+        let map_base = if self.lcdc & 0x08 != 0 { 0x1C00 } else { 0x1800 };
+        let map_y = self.scanline.wrapping_add(self.scroll_y);
+        let line_start = self.scanline as usize * LCD_WIDTH;
+
+        for x in 0..LCD_WIDTH {
+            let map_x = (x as u8).wrapping_add(self.scroll_x);
+            let tile_col = (map_x / 8) as usize;
+            let tile_row = (map_y / 8) as usize;
+            let tile_index = self.vram[map_base + tile_row * 32 + tile_col];
+
+            let tile_offset = self.tile_data_offset(tile_index);
+            let color = self.tile_pixel(tile_offset, map_y & 7, map_x & 7);
+            self.frame_buffer[line_start + x] = Self::apply_palette(self.bg_palette, color);
+        }
     }
 
     /// Render window tiles for the current scanline
     fn render_window(&mut self) {
-        // Window rendering logic will be implemented here
+        if self.scanline < self.window_y {
+            return;
+        }
+        let window_x_start = self.window_x as i16 - 7;
+        if window_x_start >= LCD_WIDTH as i16 {
+            return;
+        }
+
+        let map_base = if self.lcdc & 0x40 != 0 { 0x1C00 } else { 0x1800 };
+        let map_y = self.window_line;
+        let line_start = self.scanline as usize * LCD_WIDTH;
+        let mut drew_any = false;
+
+        for x in 0..LCD_WIDTH {
+            let window_x = x as i16 - window_x_start;
+            if window_x < 0 {
+                continue;
+            }
+            drew_any = true;
+
+            let tile_col = (window_x as usize / 8) % 32;
+            let tile_row = (map_y / 8) as usize;
+            let tile_index = self.vram[map_base + tile_row * 32 + tile_col];
+
+            let tile_offset = self.tile_data_offset(tile_index);
+            let color = self.tile_pixel(tile_offset, map_y & 7, (window_x & 7) as u8);
+            self.frame_buffer[line_start + x] = Self::apply_palette(self.bg_palette, color);
+        }
+
+        if drew_any {
+            self.window_line = self.window_line.wrapping_add(1);
+        }
     }
 
     /// Render sprites for the current scanline
     fn render_sprites(&mut self) {
-        // Sprite rendering logic will be implemented here
-        // This involves reading OAM data and rendering sprite tiles
+        let sprite_height: u8 = if self.lcdc & 0x04 != 0 { 16 } else { 8 };
+        let line_start = self.scanline as usize * LCD_WIDTH;
+
+        let mut visible: Vec<(u8, u8, u8, u8)> = Vec::with_capacity(MAX_SPRITES_PER_LINE);
+        for entry in 0..OAM_ENTRY_COUNT {
+            let base = entry * OAM_ENTRY_SIZE;
+            let sprite_y = self.oam[base].wrapping_sub(16);
+            let sprite_x = self.oam[base + 1].wrapping_sub(8);
+            let tile_index = self.oam[base + 2];
+            let attributes = self.oam[base + 3];
+
+            let row_in_sprite = self.scanline.wrapping_sub(sprite_y);
+            if row_in_sprite >= sprite_height {
+                continue;
+            }
+
+            visible.push((sprite_x, tile_index, attributes, row_in_sprite));
+            if visible.len() >= MAX_SPRITES_PER_LINE {
+                break;
+            }
+        }
+
+        // DMG priority: lower X wins; OAM index breaks ties, which the
+        // stable sort preserves since entries were pushed in OAM order.
+        visible.sort_by_key(|&(sprite_x, ..)| sprite_x);
+
+        for (sprite_x, tile_index, attributes, row_in_sprite) in visible.into_iter().rev() {
+            let y_flip = attributes & 0x40 != 0;
+            let x_flip = attributes & 0x20 != 0;
+            let use_obp1 = attributes & 0x10 != 0;
+            let behind_bg = attributes & 0x80 != 0;
+
+            let row = if y_flip {
+                sprite_height - 1 - row_in_sprite
+            } else {
+                row_in_sprite
+            };
+            let tile_index = if sprite_height == 16 {
+                tile_index & 0xFE | (if row >= 8 { 1 } else { 0 })
+            } else {
+                tile_index
+            };
+            let tile_offset = tile_index as usize * 16;
+            let row_in_tile = row & 7;
+
+            for col in 0..8u8 {
+                let pixel_x = sprite_x as i16 + col as i16;
+                if !(0..LCD_WIDTH as i16).contains(&pixel_x) {
+                    continue;
+                }
+
+                let col_in_tile = if x_flip { 7 - col } else { col };
+                let color = self.tile_pixel(tile_offset, row_in_tile, col_in_tile);
+                if color == 0 {
+                    continue; // Transparent
+                }
+                if behind_bg
+                    && self.frame_buffer[line_start + pixel_x as usize]
+                        != Self::apply_palette(self.bg_palette, 0)
+                {
+                    continue;
+                }
+
+                let palette = if use_obp1 {
+                    self.obj_palette_1
+                } else {
+                    self.obj_palette_0
+                };
+                self.frame_buffer[line_start + pixel_x as usize] =
+                    Self::apply_palette(palette, color);
+            }
+        }
     }
 
     /// Get the current frame buffer
@@ -168,6 +354,75 @@ impl Ppu {
     pub fn is_vblank(&self) -> bool {
         self.mode == PpuMode::VBlank
     }
+
+    /// Serialize VRAM, OAM, the frame buffer, and all control/timing state
+    /// for a save state.
+    pub fn save_state(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.vram);
+        buf.extend_from_slice(&self.oam);
+        buf.extend_from_slice(&self.frame_buffer);
+        buf.push(self.scanline);
+        buf.push(self.mode as u8);
+        buf.extend_from_slice(&self.cycles.to_le_bytes());
+        buf.push(self.lcdc);
+        buf.push(self.stat);
+        buf.push(self.scroll_x);
+        buf.push(self.scroll_y);
+        buf.push(self.window_x);
+        buf.push(self.window_y);
+        buf.push(self.bg_palette);
+        buf.push(self.obj_palette_0);
+        buf.push(self.obj_palette_1);
+        buf.push(self.window_line);
+    }
+
+    /// Restore state previously produced by `save_state` from the front of
+    /// `data`, returning the number of bytes consumed.
+    pub fn load_state(&mut self, data: &[u8]) -> usize {
+        let mut offset = 0;
+
+        self.vram.copy_from_slice(&data[offset..offset + 0x2000]);
+        offset += 0x2000;
+        self.oam.copy_from_slice(&data[offset..offset + 0xA0]);
+        offset += 0xA0;
+        self.frame_buffer
+            .copy_from_slice(&data[offset..offset + LCD_WIDTH * LCD_HEIGHT]);
+        offset += LCD_WIDTH * LCD_HEIGHT;
+
+        self.scanline = data[offset];
+        offset += 1;
+        self.mode = match data[offset] {
+            0 => PpuMode::HBlank,
+            1 => PpuMode::VBlank,
+            2 => PpuMode::OamScan,
+            _ => PpuMode::Drawing,
+        };
+        offset += 1;
+        self.cycles = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+        offset += 4;
+        self.lcdc = data[offset];
+        offset += 1;
+        self.stat = data[offset];
+        offset += 1;
+        self.scroll_x = data[offset];
+        offset += 1;
+        self.scroll_y = data[offset];
+        offset += 1;
+        self.window_x = data[offset];
+        offset += 1;
+        self.window_y = data[offset];
+        offset += 1;
+        self.bg_palette = data[offset];
+        offset += 1;
+        self.obj_palette_0 = data[offset];
+        offset += 1;
+        self.obj_palette_1 = data[offset];
+        offset += 1;
+        self.window_line = data[offset];
+        offset += 1;
+
+        offset
+    }
 }
 
 impl Default for Ppu {
@@ -0,0 +1,141 @@
+//! Cycle-accurate event scheduler
+//!
+//! Centralizes hardware timing so components don't each re-derive it with
+//! their own ad-hoc cycle accumulator (as `Apu::step`'s `sample_counter`
+//! does today). Components register future events against an absolute
+//! cycle count; the core loop advances the scheduler and dispatches
+//! whatever comes due, letting the CPU run in bursts between events
+//! instead of being stepped one cycle at a time.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Kinds of events the scheduler can carry.
+///
+/// New hardware timing sources (timer/serial IO, ...) are added here as
+/// they're wired up to the scheduler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventType {
+    /// APU frame sequencer tick (512 Hz, drives length/envelope/sweep).
+    ApuFrameSequencer,
+    /// Channel 1 (square + sweep) frequency timer reload.
+    ApuChannel1Timer,
+    /// Channel 2 (square) frequency timer reload.
+    ApuChannel2Timer,
+    /// Channel 3 (wave) frequency timer reload.
+    ApuChannel3Timer,
+    /// Channel 4 (noise) LFSR timer reload.
+    ApuChannel4Timer,
+    /// PPU mode transition (OAM scan / drawing / HBlank / VBlank).
+    PpuModeChange,
+}
+
+impl EventType {
+    const COUNT: usize = 6;
+
+    fn index(self) -> usize {
+        match self {
+            EventType::ApuFrameSequencer => 0,
+            EventType::ApuChannel1Timer => 1,
+            EventType::ApuChannel2Timer => 2,
+            EventType::ApuChannel3Timer => 3,
+            EventType::ApuChannel4Timer => 4,
+            EventType::PpuModeChange => 5,
+        }
+    }
+}
+
+/// Cycle-aware event scheduler.
+///
+/// Holds a min-heap of `(timestamp_cycles, EventType, generation)` entries
+/// ordered by timestamp, plus a running absolute cycle counter. `cycles` is
+/// counted since power-on rather than reset per-frame, so wraparound is a
+/// non-issue for the lifetime of a session.
+///
+/// `cancel` doesn't remove the stale heap entry directly (that would
+/// require a linear scan); instead it bumps a per-event-kind generation
+/// counter, and popped entries whose generation no longer matches the
+/// current one are silently dropped.
+pub struct Scheduler {
+    cycles: u64,
+    heap: BinaryHeap<Reverse<(u64, EventType, u64)>>,
+    generation: [u64; EventType::COUNT],
+}
+
+impl Scheduler {
+    /// Create a new scheduler with the cycle counter at zero.
+    pub fn new() -> Self {
+        Self {
+            cycles: 0,
+            heap: BinaryHeap::new(),
+            generation: [0; EventType::COUNT],
+        }
+    }
+
+    /// The number of cycles elapsed since power-on.
+    pub fn current_cycle(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Advance the absolute cycle counter. Called by the core loop after
+    /// running the CPU for a burst of cycles.
+    pub fn advance(&mut self, cycles: u64) {
+        self.cycles += cycles;
+    }
+
+    /// Register a future event `in_cycles` cycles from now.
+    pub fn schedule(&mut self, event: EventType, in_cycles: u64) {
+        let generation = self.generation[event.index()];
+        self.heap.push(Reverse((self.cycles + in_cycles, event, generation)));
+    }
+
+    /// Invalidate any pending occurrences of `event`. A future `schedule`
+    /// call for the same kind is unaffected.
+    pub fn cancel(&mut self, event: EventType) {
+        self.generation[event.index()] += 1;
+    }
+
+    /// Pop and return the next event that is due (`timestamp <= cycles`)
+    /// and not stale, or `None` if nothing is ready yet. Call repeatedly to
+    /// drain every event due at the current cycle before stepping further.
+    pub fn pop_ready(&mut self) -> Option<EventType> {
+        while let Some(&Reverse((timestamp, event, generation))) = self.heap.peek() {
+            if timestamp > self.cycles {
+                return None;
+            }
+            self.heap.pop();
+            if generation == self.generation[event.index()] {
+                return Some(event);
+            }
+            // Stale (canceled) entry - keep draining.
+        }
+        None
+    }
+
+    /// Advance `cycles` to the timestamp of the earliest pending event
+    /// (skipping stale entries) and return how many cycles that was, so
+    /// the CPU can run in a burst of that length before the next dispatch
+    /// pass. Returns `0` if there is nothing scheduled.
+    pub fn run_until_next_event(&mut self) -> u64 {
+        loop {
+            match self.heap.peek() {
+                Some(&Reverse((timestamp, event, generation))) => {
+                    if generation != self.generation[event.index()] {
+                        self.heap.pop();
+                        continue;
+                    }
+                    let delta = timestamp.saturating_sub(self.cycles);
+                    self.cycles = timestamp;
+                    return delta;
+                }
+                None => return 0,
+            }
+        }
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
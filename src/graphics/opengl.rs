@@ -2,7 +2,7 @@
 //!
 //! This module provides OpenGL-based rendering for native platforms.
 
-use super::renderer::{Renderer, RenderConfig, FilterMode};
+use super::renderer::{resolve_palette, FilterMode, RenderConfig, Renderer};
 use crate::EmulatorError;
 
 /// OpenGL renderer implementation
@@ -19,6 +19,12 @@ impl OpenGLRenderer {
             initialized: false,
         }
     }
+
+    /// Values for the `u_palette` fragment-shader uniform, resolved from
+    /// `config.palette`/`config.color_correction`.
+    pub fn palette_uniform(&self) -> [[f32; 3]; 4] {
+        resolve_palette(self.config.palette, self.config.color_correction)
+    }
 }
 
 impl Renderer for OpenGLRenderer {
@@ -32,8 +38,9 @@ impl Renderer for OpenGLRenderer {
         if !self.initialized {
             return Err(EmulatorError::GraphicsError("Renderer not initialized".to_string()));
         }
-        
-        // OpenGL rendering implementation will be added here
+
+        // OpenGL rendering implementation will be added here; it should
+        // upload `self.palette_uniform()` to `u_palette` before drawing.
         Ok(())
     }
 
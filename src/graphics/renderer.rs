@@ -108,6 +108,11 @@ pub struct RenderConfig {
     pub scale: u32,
     pub vsync: bool,
     pub filter: FilterMode,
+    /// Base 4-shade RGB palette, before `color_correction` is applied.
+    /// Defaults to `DMG_PALETTE`'s flat grayscale ramp; set to a green-tint
+    /// ramp for the classic DMG LCD look.
+    pub palette: [[f32; 3]; 4],
+    pub color_correction: ColorCorrectionMode,
 }
 
 impl Default for RenderConfig {
@@ -118,10 +123,63 @@ impl Default for RenderConfig {
             scale: 4,
             vsync: true,
             filter: FilterMode::Nearest,
+            palette: DMG_PALETTE,
+            color_correction: ColorCorrectionMode::Off,
         }
     }
 }
 
+/// Color-correction curve applied to `RenderConfig::palette` before it
+/// reaches a pixel or the `u_palette` shader uniform.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColorCorrectionMode {
+    /// Use the configured palette unmodified.
+    #[default]
+    Off,
+    /// Gamma-correct each channel (approximates the real LCD's nonlinear
+    /// response instead of a linear 0/96/192/255 ramp).
+    CorrectCurves,
+    /// `CorrectCurves` plus a narrowed output range, approximating the
+    /// washed-out blacks and dim whites of the real DMG LCD panel.
+    EmulateHardware,
+    /// Apply `CorrectCurves`, then rescale so the lightest shade lands at
+    /// (near) white.
+    PreserveBrightness,
+}
+
+/// Resolve `base` through `mode`, producing the palette that should
+/// actually be drawn with (or uploaded to `u_palette`).
+pub fn resolve_palette(base: [[f32; 3]; 4], mode: ColorCorrectionMode) -> [[f32; 3]; 4] {
+    let mut palette = base;
+    for shade in palette.iter_mut() {
+        for channel in shade.iter_mut() {
+            *channel = match mode {
+                ColorCorrectionMode::Off => *channel,
+                ColorCorrectionMode::CorrectCurves => channel.powf(2.2),
+                ColorCorrectionMode::EmulateHardware => 0.1 + channel.powf(2.2) * 0.8,
+                ColorCorrectionMode::PreserveBrightness => channel.powf(2.2),
+            };
+        }
+    }
+
+    if mode == ColorCorrectionMode::PreserveBrightness {
+        let brightest = palette
+            .iter()
+            .flatten()
+            .copied()
+            .fold(0.0_f32, f32::max);
+        if brightest > 0.0 {
+            for shade in palette.iter_mut() {
+                for channel in shade.iter_mut() {
+                    *channel = (*channel / brightest).min(1.0);
+                }
+            }
+        }
+    }
+
+    palette
+}
+
 /// Texture filtering modes
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FilterMode {
@@ -129,18 +187,25 @@ pub enum FilterMode {
     Linear,
 }
 
-/// Utility functions for texture handling
-pub fn create_texture_data(frame_buffer: &[u8]) -> Vec<u8> {
-    // Convert Game Boy pixel values to texture data
-    frame_buffer.iter().map(|&pixel| {
-        match pixel {
-            0 => 255, // White
-            1 => 192, // Light gray
-            2 => 96,  // Dark gray
-            3 => 0,   // Black
-            _ => 0,
+/// Convert Game Boy pixel values to RGB texture data through `palette` and
+/// `color_correction`, mirroring `DisplayHandler::convert_to_rgb` for
+/// callers that want the conversion without a full `DisplayHandler`.
+pub fn create_texture_data(
+    frame_buffer: &[u8],
+    palette: [[f32; 3]; 4],
+    color_correction: ColorCorrectionMode,
+) -> Vec<u8> {
+    let palette = resolve_palette(palette, color_correction);
+    let mut data = Vec::with_capacity(frame_buffer.len() * 3);
+
+    for &pixel in frame_buffer {
+        let shade = palette[(pixel & 0x03) as usize];
+        for channel in shade {
+            data.push((channel.clamp(0.0, 1.0) * 255.0).round() as u8);
         }
-    }).collect()
+    }
+
+    data
 }
 
 /// Convert pixel coordinates to normalized device coordinates
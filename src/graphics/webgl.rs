@@ -2,7 +2,7 @@
 //!
 //! This module provides WebGL-based rendering for the web platform.
 
-use super::renderer::{Renderer, RenderConfig, FilterMode};
+use super::renderer::{resolve_palette, FilterMode, RenderConfig, Renderer};
 use crate::EmulatorError;
 
 /// WebGL renderer implementation
@@ -19,6 +19,12 @@ impl WebGLRenderer {
             initialized: false,
         }
     }
+
+    /// Values for the `u_palette` fragment-shader uniform, resolved from
+    /// `config.palette`/`config.color_correction`.
+    pub fn palette_uniform(&self) -> [[f32; 3]; 4] {
+        resolve_palette(self.config.palette, self.config.color_correction)
+    }
 }
 
 impl Renderer for WebGLRenderer {
@@ -33,7 +39,8 @@ impl Renderer for WebGLRenderer {
             return Err(EmulatorError::GraphicsError("Renderer not initialized".to_string()));
         }
         
-        // WebGL rendering implementation will be added here
+        // WebGL rendering implementation will be added here; it should
+        // upload `self.palette_uniform()` to `u_palette` before drawing.
         Ok(())
     }
 
@@ -6,6 +6,8 @@
 
 use thiserror::Error;
 
+#[cfg(feature = "gdb")]
+pub mod debugger;
 pub mod emulator;
 pub mod graphics;
 pub mod platform;
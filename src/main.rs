@@ -11,13 +11,13 @@ fn main() -> Result<(), EmulatorError> {
     env_logger::init();
 
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        eprintln!("Usage: {} <rom_file>", args[0]);
+    let (rom_path, gdb_port) = parse_args(&args);
+    let rom_path = rom_path.unwrap_or_else(|| {
+        eprintln!("Usage: {} [--gdb <port>] <rom_file>", args[0]);
         std::process::exit(1);
-    }
+    });
 
-    let rom_path = &args[1];
-    let rom_data = fs::read(rom_path)
+    let rom_data = fs::read(&rom_path)
         .map_err(|e| EmulatorError::MemoryError(format!("Failed to read ROM file: {}", e)))?;
 
     let mut gameboy = GameBoy::new();
@@ -25,6 +25,23 @@ fn main() -> Result<(), EmulatorError> {
 
     println!("Game Boy emulator started");
     println!("ROM loaded: {}", rom_path);
+
+    #[cfg(feature = "gdb")]
+    if let Some(port) = gdb_port {
+        use gameboy_dmg_emulator::debugger::gdb::GdbStub;
+
+        println!("Waiting for gdb on 127.0.0.1:{port}...");
+        GdbStub::new()
+            .listen_and_serve_gameboy(&format!("127.0.0.1:{port}"), &mut gameboy)
+            .map_err(|e| EmulatorError::MemoryError(format!("gdb stub failed: {e}")))?;
+    }
+
+    #[cfg(not(feature = "gdb"))]
+    if gdb_port.is_some() {
+        eprintln!("--gdb requires building with the `gdb` feature enabled");
+        std::process::exit(1);
+    }
+
     println!("Press Ctrl+C to exit");
 
     // Simple emulation loop (will be replaced with proper platform-specific implementation)
@@ -36,3 +53,21 @@ fn main() -> Result<(), EmulatorError> {
         std::thread::sleep(std::time::Duration::from_millis(16)); // ~60 FPS
     }
 }
+
+/// Parse `[--gdb <port>] <rom_file>` out of the raw argument list, returning
+/// the ROM path (if one was given) and the requested gdb port (if any).
+fn parse_args(args: &[String]) -> (Option<String>, Option<u16>) {
+    let mut rom_path = None;
+    let mut gdb_port = None;
+
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        if arg == "--gdb" {
+            gdb_port = iter.next().and_then(|port| port.parse().ok());
+        } else {
+            rom_path = Some(arg.clone());
+        }
+    }
+
+    (rom_path, gdb_port)
+}
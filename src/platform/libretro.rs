@@ -0,0 +1,369 @@
+//! libretro core frontend
+//!
+//! Implements the subset of the libretro C ABI needed to run under
+//! RetroArch and other libretro hosts, so the crate gets distribution
+//! through every libretro frontend without a bespoke SDL/window layer.
+//! Built as a `cdylib` under the `libretro` feature (`[lib] crate-type =
+//! ["cdylib", "rlib"]`, `crate-type` gated on `--features libretro` in the
+//! workspace manifest); the functions below are the entry points a
+//! libretro host resolves by symbol name after `dlopen`-ing the library.
+
+use std::ffi::{c_char, c_void, CStr};
+use std::sync::Mutex;
+
+use crate::emulator::GameBoy;
+use crate::emulator::ppu::{LCD_HEIGHT, LCD_WIDTH};
+
+const RETRO_API_VERSION: u32 = 1;
+
+// Device IDs for `RETRO_DEVICE_JOYPAD`, per libretro.h.
+const RETRO_DEVICE_ID_JOYPAD_B: u32 = 0;
+const RETRO_DEVICE_ID_JOYPAD_Y: u32 = 1;
+const RETRO_DEVICE_ID_JOYPAD_SELECT: u32 = 2;
+const RETRO_DEVICE_ID_JOYPAD_START: u32 = 3;
+const RETRO_DEVICE_ID_JOYPAD_UP: u32 = 4;
+const RETRO_DEVICE_ID_JOYPAD_DOWN: u32 = 5;
+const RETRO_DEVICE_ID_JOYPAD_LEFT: u32 = 6;
+const RETRO_DEVICE_ID_JOYPAD_RIGHT: u32 = 7;
+const RETRO_DEVICE_ID_JOYPAD_A: u32 = 8;
+
+const RETRO_DEVICE_JOYPAD: u32 = 1;
+
+/// `RETRO_MEMORY_SAVE_RAM`, per libretro.h - the battery-backed cartridge
+/// RAM a host persists as a `.srm` file.
+const RETRO_MEMORY_SAVE_RAM: u32 = 0;
+
+type RetroEnvironmentCallback = extern "C" fn(cmd: u32, data: *mut c_void) -> bool;
+type RetroVideoRefreshCallback =
+    extern "C" fn(data: *const c_void, width: u32, height: u32, pitch: usize);
+type RetroAudioSampleBatchCallback = extern "C" fn(data: *const i16, frames: usize) -> usize;
+type RetroInputPollCallback = extern "C" fn();
+type RetroInputStateCallback = extern "C" fn(port: u32, device: u32, index: u32, id: u32) -> i16;
+
+#[repr(C)]
+pub struct RetroGameGeometry {
+    pub base_width: u32,
+    pub base_height: u32,
+    pub max_width: u32,
+    pub max_height: u32,
+    pub aspect_ratio: f32,
+}
+
+#[repr(C)]
+pub struct RetroSystemTiming {
+    pub fps: f64,
+    pub sample_rate: f64,
+}
+
+#[repr(C)]
+pub struct RetroSystemAvInfo {
+    pub geometry: RetroGameGeometry,
+    pub timing: RetroSystemTiming,
+}
+
+#[repr(C)]
+pub struct RetroGameInfo {
+    pub path: *const c_char,
+    pub data: *const c_void,
+    pub size: usize,
+    pub meta: *const c_char,
+}
+
+/// All state a libretro host needs bound to this core. Held in a global
+/// `Mutex` because the libretro ABI is a flat set of `extern "C"`
+/// functions with no instance handle to thread state through.
+struct LibretroCore {
+    gameboy: GameBoy,
+    video_refresh: Option<RetroVideoRefreshCallback>,
+    audio_sample_batch: Option<RetroAudioSampleBatchCallback>,
+    input_poll: Option<RetroInputPollCallback>,
+    input_state: Option<RetroInputStateCallback>,
+    frame_rgba: Vec<u8>,
+}
+
+impl LibretroCore {
+    fn new() -> Self {
+        Self {
+            gameboy: GameBoy::new(),
+            video_refresh: None,
+            audio_sample_batch: None,
+            input_poll: None,
+            input_state: None,
+            frame_rgba: vec![0u8; LCD_WIDTH * LCD_HEIGHT * 4],
+        }
+    }
+
+    /// Convert the Game Boy's grayscale shade indices (0-3) into XRGB8888,
+    /// the pixel format libretro's `retro_video_refresh` expects.
+    fn convert_frame_to_xrgb8888(&mut self) {
+        let frame_buffer = self.gameboy.get_frame_buffer();
+        for (i, &shade) in frame_buffer.iter().enumerate() {
+            let gray: u8 = match shade {
+                0 => 255,
+                1 => 192,
+                2 => 96,
+                3 => 0,
+                _ => 0,
+            };
+            let offset = i * 4;
+            self.frame_rgba[offset] = gray; // B
+            self.frame_rgba[offset + 1] = gray; // G
+            self.frame_rgba[offset + 2] = gray; // R
+            self.frame_rgba[offset + 3] = 0xFF; // X
+        }
+    }
+
+    /// Poll the four-button-plus-dpad joypad state through the host's
+    /// `retro_input_state` callback and feed it to the emulator's input.
+    fn poll_input(&mut self) {
+        let (Some(poll), Some(state)) = (self.input_poll, self.input_state) else {
+            return;
+        };
+        poll();
+
+        let pressed = |id: u32| state(0, RETRO_DEVICE_JOYPAD, 0, id) != 0;
+        let up = pressed(RETRO_DEVICE_ID_JOYPAD_UP);
+        let down = pressed(RETRO_DEVICE_ID_JOYPAD_DOWN);
+        let left = pressed(RETRO_DEVICE_ID_JOYPAD_LEFT);
+        let right = pressed(RETRO_DEVICE_ID_JOYPAD_RIGHT);
+        let a = pressed(RETRO_DEVICE_ID_JOYPAD_A);
+        let b = pressed(RETRO_DEVICE_ID_JOYPAD_B);
+        let start = pressed(RETRO_DEVICE_ID_JOYPAD_START);
+        let select = pressed(RETRO_DEVICE_ID_JOYPAD_SELECT);
+        // The Y button has no Game Boy equivalent and is intentionally
+        // unused. Active-low: a pressed button clears its bit.
+        let mut button_state = 0xFFu8;
+        for (pressed, bit) in [
+            (right, 0),
+            (left, 1),
+            (up, 2),
+            (down, 3),
+            (a, 4),
+            (b, 5),
+            (select, 6),
+            (start, 7),
+        ] {
+            if pressed {
+                button_state &= !(1 << bit);
+            }
+        }
+        self.gameboy.set_button_state(button_state);
+    }
+}
+
+static CORE: Mutex<Option<LibretroCore>> = Mutex::new(None);
+
+#[no_mangle]
+pub extern "C" fn retro_api_version() -> u32 {
+    RETRO_API_VERSION
+}
+
+#[no_mangle]
+pub extern "C" fn retro_init() {
+    *CORE.lock().unwrap() = Some(LibretroCore::new());
+}
+
+#[no_mangle]
+pub extern "C" fn retro_deinit() {
+    *CORE.lock().unwrap() = None;
+}
+
+#[no_mangle]
+pub extern "C" fn retro_set_environment(_callback: RetroEnvironmentCallback) {
+    // No optional capabilities (core options, variables, ...) are
+    // negotiated yet; every call is ignored for now.
+}
+
+#[no_mangle]
+pub extern "C" fn retro_set_video_refresh(callback: RetroVideoRefreshCallback) {
+    if let Some(core) = CORE.lock().unwrap().as_mut() {
+        core.video_refresh = Some(callback);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn retro_set_audio_sample_batch(callback: RetroAudioSampleBatchCallback) {
+    if let Some(core) = CORE.lock().unwrap().as_mut() {
+        core.audio_sample_batch = Some(callback);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn retro_set_input_poll(callback: RetroInputPollCallback) {
+    if let Some(core) = CORE.lock().unwrap().as_mut() {
+        core.input_poll = Some(callback);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn retro_set_input_state(callback: RetroInputStateCallback) {
+    if let Some(core) = CORE.lock().unwrap().as_mut() {
+        core.input_state = Some(callback);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn retro_get_system_av_info(info: *mut RetroSystemAvInfo) {
+    if info.is_null() {
+        return;
+    }
+    unsafe {
+        (*info).geometry = RetroGameGeometry {
+            base_width: LCD_WIDTH as u32,
+            base_height: LCD_HEIGHT as u32,
+            max_width: LCD_WIDTH as u32,
+            max_height: LCD_HEIGHT as u32,
+            aspect_ratio: LCD_WIDTH as f32 / LCD_HEIGHT as f32,
+        };
+        (*info).timing = RetroSystemTiming {
+            fps: 59.727_5,
+            sample_rate: crate::emulator::apu::SAMPLE_RATE as f64,
+        };
+    }
+}
+
+/// # Safety
+/// `game` must be a valid pointer to a `retro_game_info` (or null) for the
+/// duration of this call, per the libretro ABI contract.
+#[no_mangle]
+pub unsafe extern "C" fn retro_load_game(game: *const RetroGameInfo) -> bool {
+    if game.is_null() {
+        return false;
+    }
+    let game = &*game;
+    if game.data.is_null() || game.size == 0 {
+        return false;
+    }
+    let rom_data = std::slice::from_raw_parts(game.data as *const u8, game.size);
+
+    let mut guard = CORE.lock().unwrap();
+    let core = guard.get_or_insert_with(LibretroCore::new);
+    core.gameboy.load_rom(rom_data).is_ok()
+}
+
+#[no_mangle]
+pub extern "C" fn retro_unload_game() {
+    if let Some(core) = CORE.lock().unwrap().as_mut() {
+        core.gameboy = GameBoy::new();
+    }
+}
+
+/// Run one frame (~70224 cycles), then push video and audio to the host
+/// through `retro_video_refresh` and `retro_audio_sample_batch`.
+#[no_mangle]
+pub extern "C" fn retro_run() {
+    let mut guard = CORE.lock().unwrap();
+    let Some(core) = guard.as_mut() else {
+        return;
+    };
+
+    core.poll_input();
+
+    core.gameboy.run_cycles(70224);
+
+    core.convert_frame_to_xrgb8888();
+    if let Some(video_refresh) = core.video_refresh {
+        video_refresh(
+            core.frame_rgba.as_ptr() as *const c_void,
+            LCD_WIDTH as u32,
+            LCD_HEIGHT as u32,
+            LCD_WIDTH * 4,
+        );
+    }
+
+    if let Some(audio_sample_batch) = core.audio_sample_batch {
+        let samples = core.gameboy.get_audio_samples();
+        if !samples.is_empty() {
+            audio_sample_batch(samples.as_ptr(), samples.len() / 2);
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn retro_reset() {
+    if let Some(core) = CORE.lock().unwrap().as_mut() {
+        core.gameboy = GameBoy::new();
+    }
+}
+
+/// # Safety
+/// `system_info` must be a valid, non-null pointer to a
+/// `retro_system_info` for the duration of this call.
+#[no_mangle]
+pub unsafe extern "C" fn retro_get_system_info(system_info: *mut RetroSystemInfo) {
+    if system_info.is_null() {
+        return;
+    }
+    (*system_info) = RetroSystemInfo {
+        library_name: b"Game Boy DMG\0".as_ptr() as *const c_char,
+        library_version: b"0.1.0\0".as_ptr() as *const c_char,
+        valid_extensions: b"gb\0".as_ptr() as *const c_char,
+        need_fullpath: false,
+        block_extract: false,
+    };
+}
+
+#[repr(C)]
+pub struct RetroSystemInfo {
+    pub library_name: *const c_char,
+    pub library_version: *const c_char,
+    pub valid_extensions: *const c_char,
+    pub need_fullpath: bool,
+    pub block_extract: bool,
+}
+
+/// Battery-backed save RAM exposed to the host so it can persist `.srm`
+/// files itself instead of the core managing save files directly.
+#[no_mangle]
+pub extern "C" fn retro_get_memory_data(id: u32) -> *mut c_void {
+    if id != RETRO_MEMORY_SAVE_RAM {
+        return std::ptr::null_mut();
+    }
+    match CORE.lock().unwrap().as_mut() {
+        Some(core) => core.gameboy.save_ram_mut().as_mut_ptr() as *mut c_void,
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn retro_get_memory_size(id: u32) -> usize {
+    if id != RETRO_MEMORY_SAVE_RAM {
+        return 0;
+    }
+    match CORE.lock().unwrap().as_mut() {
+        Some(core) => core.gameboy.save_ram_mut().len(),
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn retro_serialize_size() -> usize {
+    0
+}
+
+/// # Safety
+/// `_data` must point to at least `_size` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn retro_serialize(_data: *mut c_void, _size: usize) -> bool {
+    false
+}
+
+/// # Safety
+/// `_data` must point to at least `_size` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn retro_unserialize(_data: *const c_void, _size: usize) -> bool {
+    false
+}
+
+#[no_mangle]
+pub extern "C" fn retro_set_controller_port_device(_port: u32, _device: u32) {}
+
+#[no_mangle]
+pub extern "C" fn retro_cheat_reset() {}
+
+/// # Safety
+/// `code` must be a valid, nul-terminated C string pointer.
+#[no_mangle]
+pub unsafe extern "C" fn retro_cheat_set(_index: u32, _enabled: bool, code: *const c_char) {
+    let _ = CStr::from_ptr(code);
+}
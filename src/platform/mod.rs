@@ -6,4 +6,7 @@
 pub mod web;
 
 #[cfg(not(target_arch = "wasm32"))]
-pub mod native;
\ No newline at end of file
+pub mod native;
+
+#[cfg(feature = "libretro")]
+pub mod libretro;
\ No newline at end of file
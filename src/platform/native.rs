@@ -5,6 +5,7 @@
 use std::time::{Duration, Instant};
 
 use crate::emulator::GameBoy;
+use crate::graphics::renderer::{create_texture_data, ColorCorrectionMode, DMG_PALETTE};
 use crate::EmulatorError;
 
 /// Native Game Boy emulator wrapper
@@ -36,9 +37,7 @@ impl NativeGameBoy {
         // Only run if enough time has passed
         if now.duration_since(self.last_frame_time) >= self.target_frame_time {
             // Run emulation for one frame (approximately 70224 cycles)
-            for _ in 0..70224 {
-                self.gameboy.step();
-            }
+            self.gameboy.run_cycles(70224);
             
             self.last_frame_time = now;
         }
@@ -50,7 +49,7 @@ impl NativeGameBoy {
     }
 
     /// Get audio samples for the current frame
-    pub fn get_audio_samples(&self) -> &[i16] {
+    pub fn get_audio_samples(&mut self) -> &[i16] {
         self.gameboy.get_audio_samples()
     }
 
@@ -171,42 +170,39 @@ pub struct DisplayHandler {
     width: u32,
     height: u32,
     scale: u32,
+    palette: [[f32; 3]; 4],
+    color_correction: ColorCorrectionMode,
 }
 
 impl DisplayHandler {
-    /// Create a new display handler
+    /// Create a new display handler using the default grayscale palette
+    /// with no color correction.
     pub fn new(scale: u32) -> Self {
         Self {
             width: 160,
             height: 144,
             scale,
+            palette: DMG_PALETTE,
+            color_correction: ColorCorrectionMode::Off,
         }
     }
 
+    /// Set the palette and color-correction curve `convert_to_rgb` draws
+    /// with.
+    pub fn set_palette(&mut self, palette: [[f32; 3]; 4], color_correction: ColorCorrectionMode) {
+        self.palette = palette;
+        self.color_correction = color_correction;
+    }
+
     /// Get the display dimensions
     pub fn get_dimensions(&self) -> (u32, u32) {
         (self.width * self.scale, self.height * self.scale)
     }
 
-    /// Convert Game Boy pixel data to RGB
+    /// Convert Game Boy pixel data to RGB, through the configured palette
+    /// and color-correction mode.
     pub fn convert_to_rgb(&self, frame_buffer: &[u8]) -> Vec<u8> {
-        let mut rgb_data = Vec::with_capacity(frame_buffer.len() * 3);
-        
-        for &pixel in frame_buffer {
-            let color = match pixel {
-                0 => 255, // White
-                1 => 192, // Light gray
-                2 => 96,  // Dark gray
-                3 => 0,   // Black
-                _ => 0,
-            };
-            
-            rgb_data.push(color); // Red
-            rgb_data.push(color); // Green
-            rgb_data.push(color); // Blue
-        }
-        
-        rgb_data
+        create_texture_data(frame_buffer, self.palette, self.color_correction)
     }
 
     /// Scale pixel data for display
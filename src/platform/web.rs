@@ -69,9 +69,7 @@ impl WebGameBoy {
     #[wasm_bindgen]
     pub fn run_frame(&mut self) {
         // Run emulation for one frame (approximately 70224 cycles)
-        for _ in 0..70224 {
-            self.gameboy.step();
-        }
+        self.gameboy.run_cycles(70224);
     }
 
     /// Render the current frame to the canvas
@@ -146,7 +144,7 @@ impl WebGameBoy {
 
     /// Get audio samples for the current frame
     #[wasm_bindgen]
-    pub fn get_audio_samples(&self) -> js_sys::Float32Array {
+    pub fn get_audio_samples(&mut self) -> js_sys::Float32Array {
         let samples = self.gameboy.get_audio_samples();
         let float_samples: Vec<f32> = samples.iter().map(|&s| s as f32 / 32768.0).collect();
         js_sys::Float32Array::from(float_samples.as_slice())
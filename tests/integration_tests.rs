@@ -1,9 +1,11 @@
+use gameboy_dmg_emulator::emulator::memory::Mmu;
+use gameboy_dmg_emulator::emulator::ppu::{LCD_HEIGHT, LCD_WIDTH};
 use gameboy_dmg_emulator::{GameBoy, EmulatorError};
 
 #[test]
 fn test_gameboy_creation() {
-    let gameboy = GameBoy::new();
-    assert_eq!(gameboy.get_frame_buffer().len(), 0);
+    let mut gameboy = GameBoy::new();
+    assert_eq!(gameboy.get_frame_buffer().len(), LCD_WIDTH * LCD_HEIGHT);
     assert_eq!(gameboy.get_audio_samples().len(), 0);
 }
 
@@ -23,9 +25,70 @@ fn test_rom_loading() {
 #[test]
 fn test_emulator_step() {
     let mut gameboy = GameBoy::new();
-    
+
     // Should not panic
     gameboy.step();
     gameboy.step();
     gameboy.step();
+}
+
+#[test]
+fn test_save_state_round_trip() {
+    let mut gameboy = GameBoy::new();
+    gameboy.load_rom(&vec![0; 0x8000]).unwrap();
+    for _ in 0..100 {
+        gameboy.step();
+    }
+    let snapshot = gameboy.save_state();
+
+    // Diverge from the snapshot, then restore it and confirm the state is
+    // byte-for-byte identical to what was saved.
+    for _ in 0..100 {
+        gameboy.step();
+    }
+    assert_ne!(gameboy.save_state(), snapshot);
+
+    gameboy.load_state(&snapshot).unwrap();
+    assert_eq!(gameboy.save_state(), snapshot);
+}
+
+/// A minimal MBC1+RAM+BATTERY ROM (8 x 16KB banks, 8KB RAM) so bank
+/// switching has somewhere to switch to.
+fn mbc1_rom_with_battery() -> Vec<u8> {
+    let mut rom = vec![0u8; 0x20000];
+    rom[0x147] = 0x03; // MBC1+RAM+BATTERY
+    rom[0x148] = 0x02; // 128KB ROM (8 banks)
+    rom[0x149] = 0x02; // 8KB RAM
+    rom
+}
+
+#[test]
+fn test_mmu_save_state_round_trip_preserves_mbc1_bank_registers() {
+    let mut mmu = Mmu::new();
+    mmu.load_rom(&mbc1_rom_with_battery()).unwrap();
+
+    mmu.write_byte(0x0000, 0x0A); // enable external RAM
+    mmu.write_byte(0x2000, 0x03); // select ROM bank 3
+    let mut snapshot = Vec::new();
+    mmu.save_state(&mut snapshot);
+
+    // Switch away from the snapshotted bank, confirm the state actually
+    // diverged, then restore the snapshot and confirm bank 3 is selected
+    // again rather than whatever bank was active at load time.
+    mmu.write_byte(0x2000, 0x01);
+    let mut diverged = Vec::new();
+    mmu.save_state(&mut diverged);
+    assert_ne!(diverged, snapshot);
+
+    mmu.load_state(&snapshot);
+    let mut restored = Vec::new();
+    mmu.save_state(&mut restored);
+    assert_eq!(restored, snapshot);
+
+    // Two machines with a different bank switched in must not compare equal.
+    let mut other = Mmu::new();
+    other.load_rom(&mbc1_rom_with_battery()).unwrap();
+    other.write_byte(0x0000, 0x0A);
+    other.write_byte(0x2000, 0x01);
+    assert!(mmu != other);
 }
\ No newline at end of file